@@ -0,0 +1,88 @@
+use crate::number_theory::is_prime;
+use crate::{inverse, mod_pow};
+
+//dev:      Primes below this use Fermat's little theorem. Above it the exponent p - 2 needs more than 64
+//          square-and-multiply rounds, each a 128 bit division, and the Euclidean loop wins.
+const FERMAT_LIMIT: u64 = 1 << 32;
+
+//notice:   Different moduli allow different shortcuts:
+//              m = 2^k     Hensel lifting (Newton's method) on the bits, which needs no division at all
+//              m prime     Fermat's little theorem, a^(p-2) ≅ a^-1 (mod p)
+//              otherwise   the Extended Euclidean Algorithm, which works for every modulus
+//dev:      This function looks at m, picks the method that suits it best, and uses it to calculate the inverse.
+//          The checks are cheap next to the inverse itself: m.is_power_of_two() is a single instruction,
+//          and primality is only tested for moduli small enough to use Fermat.
+//returns:  Returns Some(inverse) if a has an inverse under Mod m, None otherwise (including when m is 0).
+pub fn inverse_auto(a: u64, m: u64) -> Option<u64> {
+    inverse_auto_explained(a, m).0
+}
+
+//dev:      This function is inverse_auto, but also says which method it picked, which is useful for understanding
+//          the choice and for benchmarking each method separately.
+//returns:  Returns the inverse (as inverse_auto does) and the method: "hensel-pow2", "fermat" or "extended-euclid".
+pub fn inverse_auto_explained(a: u64, m: u64) -> (Option<u64>, &'static str) {
+    if m >= 2 && m.is_power_of_two() {
+        (inverse_pow2(a, m), "hensel-pow2")
+    } else if m < FERMAT_LIMIT && is_prime(m) {
+        (inverse_fermat(a, m), "fermat")
+    } else {
+        (inverse(a, m), "extended-euclid")
+    }
+}
+
+//notice:   If x is the inverse of a Mod 2^k, then x * (2 - a * x) is the inverse Mod 2^(2k), so every round doubles
+//          the number of correct bits. Any odd a is its own inverse Mod 8 (e.g. 3 * 3 = 9 ≅ 1), so starting from
+//          x = a gives 3 correct bits, and 5 rounds give 96, more than the 64 a u64 holds.
+//Assumption:   Assumes m is a power of two and at least 2
+fn inverse_pow2(a: u64, m: u64) -> Option<u64> {
+    //only the odd numbers are relatively prime to a power of two
+    if a.is_multiple_of(2) {
+        return None;
+    }
+
+    //the arithmetic is Mod 2^64, which wrapping gives for free, and 2^64 is a multiple of m
+    let mut x = a;
+    for _ in 0..5 {
+        x = x.wrapping_mul(2u64.wrapping_sub(a.wrapping_mul(x)));
+    }
+
+    Some(x & (m - 1))
+}
+
+//notice:   Fermat's little theorem: a^(p-1) ≅ 1 (mod p) for a prime p that doesn't divide a, so a^(p-2) is the inverse.
+//Assumption:   Assumes p is prime
+fn inverse_fermat(a: u64, p: u64) -> Option<u64> {
+    if a.is_multiple_of(p) {
+        return None;
+    }
+
+    Some(mod_pow(a, p - 2, p))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_reference_inverse_for_every_kind_of_modulus() {
+        //prime, power of two, composite, and a prime too big for Fermat
+        for m in [2, 7, 97, 65_521, 1 << 10, 1 << 63, 12, 100, 561, 1 << 33, 4_294_967_311] {
+            for a in [0, 1, 2, 3, 5, 6, 10, 99, 12_345, u64::MAX] {
+                assert_eq!(inverse_auto(a, m), inverse(a, m), "a = {}, m = {}", a, m);
+            }
+        }
+        assert_eq!(inverse_auto(3, 0), None);
+        assert_eq!(inverse_auto(3, 1), inverse(3, 1));
+    }
+
+    #[test]
+    fn explains_which_method_it_picked() {
+        assert_eq!(inverse_auto_explained(3, 7), (Some(5), "fermat"));
+        assert_eq!(inverse_auto_explained(3, 1 << 16), (inverse(3, 1 << 16), "hensel-pow2"));
+        assert_eq!(inverse_auto_explained(4, 1 << 16), (None, "hensel-pow2"));
+        assert_eq!(inverse_auto_explained(7, 100), (Some(43), "extended-euclid"));
+        //4294967311 is prime, but past the Fermat limit
+        assert_eq!(inverse_auto_explained(2, 4_294_967_311).1, "extended-euclid");
+        assert_eq!(inverse_auto_explained(3, 0), (None, "extended-euclid"));
+    }
+}
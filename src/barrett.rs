@@ -0,0 +1,152 @@
+//notice:   Barrett reduction replaces the division in x mod m by multiplications. With mu = floor((2^128 - 1) / m)
+//          worked out once, x * mu / 2^128 is within a couple of units of the true quotient x / m, so subtracting
+//          that many m from x and then at most a few more leaves x mod m.
+//dev:      The precomputed values for reducing under one fixed modulus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BarrettCtx {
+    modulus: u64,
+    mu: u128,
+}
+
+impl BarrettCtx {
+    //dev:      Precomputes the reciprocal of modulus. Panics if modulus is 0.
+    pub fn new(modulus: u64) -> Self {
+        if modulus == 0 {
+            panic!("the modulus of a BarrettCtx must be greater than 0");
+        }
+
+        BarrettCtx { modulus, mu: u128::MAX / modulus as u128 }
+    }
+
+    pub fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    //returns:  Returns x mod modulus, without dividing
+    pub fn reduce(&self, x: u128) -> u64 {
+        let m = self.modulus as u128;
+        let q = mul_high(x, self.mu);
+        let mut r = x - q * m;
+
+        //q falls short of the real quotient by at most 2
+        while r >= m {
+            r -= m;
+        }
+
+        r as u64
+    }
+
+    //returns:  Returns (a * b) mod modulus
+    pub fn mul(&self, a: u64, b: u64) -> u64 {
+        self.reduce(a as u128 * b as u128)
+    }
+
+    //returns:  Returns base^exp mod modulus, by square-and-multiply like mod_pow
+    pub fn pow(&self, base: u64, exp: u64) -> u64 {
+        let mut result = self.reduce(1);
+        let mut base = self.reduce(base as u128);
+        let mut exp = exp;
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = self.mul(result, base);
+            }
+            base = self.mul(base, base);
+            exp >>= 1;
+        }
+
+        result
+    }
+
+    //dev:      The inverse of a under the fixed modulus, by the Extended Euclidean Algorithm with the coefficients
+    //          kept in [0, modulus). Each coefficient update x - q * y is reduced with Barrett reduction instead of
+    //          being carried in an i128, so the loop works in u64 and never divides by the modulus.
+    //          The quotients of the rows still need a real division, since their divisors change every row.
+    //returns:  Returns Some(inverse), or None if a isn't relatively prime to the modulus.
+    #[allow(non_snake_case)]
+    pub fn inverse(&self, a: u64) -> Option<u64> {
+        let m = self.modulus;
+        let mut x = 0u64;
+        let mut y = 1u64;
+        let mut A = m;
+        let mut B = self.reduce(a as u128);
+
+        while B > 0 {
+            let q = A / B;
+            let r = A - q * B;
+            let qy = self.mul(q, y);
+            let t = if x >= qy { x - qy } else { m - (qy - x) };
+
+            A = B;
+            B = r;
+            x = y;
+            y = t;
+        }
+
+        //A holds the gcd. Mod 1 there is nothing but 0, so the inverse is 0
+        if A != 1 && m != 1 {
+            return None;
+        }
+
+        Some(self.reduce(x as u128))
+    }
+}
+
+//returns:  Returns the top 128 bits of the 256 bit product a * b
+fn mul_high(a: u128, b: u128) -> u128 {
+    const LOW: u128 = u64::MAX as u128;
+
+    let (a_hi, a_lo) = (a >> 64, a & LOW);
+    let (b_hi, b_lo) = (b >> 64, b & LOW);
+
+    let lo_lo = a_lo * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_lo = a_hi * b_lo;
+    let hi_hi = a_hi * b_hi;
+
+    //the middle column collects the carries coming out of the low 128 bits
+    let middle = (lo_lo >> 64) + (lo_hi & LOW) + (hi_lo & LOW);
+
+    hi_hi + (lo_hi >> 64) + (hi_lo >> 64) + (middle >> 64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reduce_matches_remainder() {
+        for &m in &[1u64, 2, 3, 7, 1_000_000_007, (1 << 32) + 15, u64::MAX - 58, u64::MAX] {
+            let ctx = BarrettCtx::new(m);
+            let m128 = m as u128;
+            let samples = [0, 1, m128 - 1, m128, m128 + 1, 2 * m128 - 1, m128 * m128 - 1, m128 * m128, u128::MAX - 1, u128::MAX];
+            for &x in samples.iter().chain(&[u64::MAX as u128, 0xDEAD_BEEF_0123_4567_89AB_CDEF]) {
+                assert_eq!(ctx.reduce(x) as u128, x % m128, "x = {}, m = {}", x, m);
+            }
+        }
+    }
+
+    #[test]
+    fn mul_and_pow_match_the_plain_versions() {
+        let ctx = BarrettCtx::new(1_000_000_007);
+        assert_eq!(ctx.mul(u64::MAX, u64::MAX), crate::mod_mul(u64::MAX, u64::MAX, 1_000_000_007));
+        assert_eq!(ctx.pow(3, 1_000_000_005), crate::mod_pow(3, 1_000_000_005, 1_000_000_007));
+        assert_eq!(BarrettCtx::new(1).pow(5, 0), 0);
+    }
+
+    #[test]
+    fn inverse_matches_try_inverse() {
+        for &m in &[1u64, 2, 10, 97, 1_000_000_007, u64::MAX] {
+            let ctx = BarrettCtx::new(m);
+            for a in (0..200).chain([m.wrapping_sub(1), u64::MAX]) {
+                assert_eq!(ctx.inverse(a), crate::try_inverse(a, m).ok(), "a = {}, m = {}", a, m);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "greater than 0")]
+    fn zero_modulus_panics() {
+        BarrettCtx::new(0);
+    }
+}
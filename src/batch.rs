@@ -0,0 +1,326 @@
+use crate::{inverse, mod_mul};
+use std::collections::HashMap;
+use std::ops::Range;
+use std::time::{Duration, Instant};
+
+//dev:      This function calculates the inverse of the same a under every modulus in the list, one after the other.
+//returns:  Returns one entry per modulus, in the same order: Some(inverse) or None if a has no inverse under it.
+pub fn inverse_across_moduli(a: u64, moduli: &[u64]) -> Vec<Option<u64>> {
+    moduli.iter().map(|&m| inverse(a, m)).collect()
+}
+
+//dev:      This function gives the same answer as inverse_across_moduli, but with the rayon feature it works through
+//          the moduli in parallel on rayon's thread pool. Each inverse is independent of the others, and collect
+//          keeps them in input order, so no synchronisation is needed.
+//returns:  Returns one entry per modulus, in the same order as the input.
+#[cfg(feature = "rayon")]
+pub fn inverse_across_moduli_parallel(a: u64, moduli: &[u64]) -> Vec<Option<u64>> {
+    use rayon::prelude::*;
+
+    moduli.par_iter().map(|&m| inverse(a, m)).collect()
+}
+
+//dev:      Without the rayon feature there is no thread pool, so this falls back to the sequential version.
+//returns:  Returns one entry per modulus, in the same order as the input.
+#[cfg(not(feature = "rayon"))]
+pub fn inverse_across_moduli_parallel(a: u64, moduli: &[u64]) -> Vec<Option<u64>> {
+    inverse_across_moduli(a, moduli)
+}
+
+//notice:   The product a_1 * ... * a_i has an inverse exactly when every factor has one, and
+//          (a_1 * ... * a_i)^-1 = (a_1 * ... * a_(i-1))^-1 * a_i^-1, so each step only needs the inverse of the new value.
+//dev:      This function calculates, for every prefix of values, the inverse of its product under Mod m.
+//          This lets a stream of values be divided out one at a time.
+//returns:  Returns a list where entry i is the inverse of values[0] * ... * values[i], or None if any value has no inverse.
+pub fn running_inverse(values: &[u64], m: u64) -> Option<Vec<u64>> {
+    let mut running = 1 % m.max(1);
+    let mut inverses = Vec::with_capacity(values.len());
+
+    for &value in values {
+        running = mod_mul(running, inverse(value, m)?, m);
+        inverses.push(running);
+    }
+
+    Some(inverses)
+}
+
+//notice:   Inverse factorials are usually worked out backwards: invert n! once, then (i-1)!^-1 = i!^-1 * i.
+//          The same trick works for the prefix products of any list: if P_i = values[0] * ... * values[i], then
+//          P_(i-1)^-1 = P_i^-1 * values[i], so only the last prefix product ever has to be inverted.
+//dev:      This function gives the same answer as running_inverse, but with one inverse and about 2n multiplications
+//          instead of n inverses: a forward pass builds the prefix products, and a backward pass turns the inverse
+//          of the last one into the inverses of all the others.
+//returns:  Returns a list where entry i is the inverse of values[0] * ... * values[i], or None if any value has no inverse.
+pub fn prefix_product_inverses(values: &[u64], m: u64) -> Option<Vec<u64>> {
+    if m == 0 {
+        return None;
+    }
+
+    let mut inverses = Vec::with_capacity(values.len());
+    let mut product = 1 % m;
+    for &value in values {
+        product = mod_mul(product, value, m);
+        inverses.push(product);
+    }
+
+    //the product of all the values has an inverse exactly when each value does
+    let mut running = inverse(product, m)?;
+    for i in (0..values.len()).rev() {
+        inverses[i] = running;
+        running = mod_mul(running, values[i], m);
+    }
+
+    Some(inverses)
+}
+
+//dev:      This function calculates the inverse of a under Mod m for every (a, m) pair and counts the pairs
+//          that have no inverse, which gives a quick health figure for a whole batch.
+//returns:  Returns one entry per pair, in the same order, together with the number of entries that are None.
+pub fn batch_inverse_with_report(pairs: &[(u64, u64)]) -> (Vec<Option<u64>>, usize) {
+    let inverses: Vec<Option<u64>> = pairs.iter().map(|&(a, m)| inverse(a, m)).collect();
+    let failures = inverses.iter().filter(|entry| entry.is_none()).count();
+
+    (inverses, failures)
+}
+
+//dev:      This function calculates the inverse of a under Mod m for every (a, m) pair, like batch_inverse_with_report,
+//          and calls progress(done, total) along the way so a long job can drive a progress bar.
+//          progress is called about 100 times at most, after each block of pairs, with done increasing every time
+//          and the last call always being (total, total). An empty batch gets a single (0, 0) call.
+//returns:  Returns one entry per pair, in the same order: Some(inverse) or None if it doesn't exist.
+pub fn batch_inverse_progress(pairs: &[(u64, u64)], mut progress: impl FnMut(usize, usize)) -> Vec<Option<u64>> {
+    let total = pairs.len();
+    if total == 0 {
+        progress(0, 0);
+        return Vec::new();
+    }
+
+    let block = total.div_ceil(100);
+    let mut inverses = Vec::with_capacity(total);
+
+    for chunk in pairs.chunks(block) {
+        inverses.extend(chunk.iter().map(|&(a, m)| inverse(a, m)));
+        progress(inverses.len(), total);
+    }
+
+    inverses
+}
+
+//dev:      This function calculates inverses for the pairs in order until the time budget runs out, so a caller
+//          with a latency limit gets whatever fits instead of waiting for the whole batch. The clock is checked
+//          before each pair, so the budget can only be overrun by the time of a single inverse.
+//returns:  Returns the inverses calculated so far (entry i belongs to pairs[i]) and how many pairs were processed,
+//          which is the length of that list. When it is less than pairs.len(), the rest can be resumed from there.
+pub fn batch_inverse_budgeted(pairs: &[(u64, u64)], budget: Duration) -> (Vec<Option<u64>>, usize) {
+    let start = Instant::now();
+    let mut inverses = Vec::new();
+
+    for &(a, m) in pairs {
+        if start.elapsed() >= budget {
+            break;
+        }
+        inverses.push(inverse(a, m));
+    }
+
+    let processed = inverses.len();
+    (inverses, processed)
+}
+
+//notice:   The inverse of a unit is itself a unit, and if b is the inverse of a then a is the inverse of b.
+//dev:      This function pairs every unit (e.g. from units_mod(m)) with its inverse. Whenever the inverse of a is
+//          calculated, the inverse of that inverse comes for free, so only about half of the inverses are calculated.
+//returns:  Returns (unit, inverse) pairs in the same order as units. Entries that have no inverse are left out.
+pub fn pair_unit_inverses(units: &[u64], m: u64) -> Vec<(u64, u64)> {
+    let mut known: HashMap<u64, u64> = HashMap::with_capacity(units.len());
+    let mut pairs = Vec::with_capacity(units.len());
+
+    for &unit in units {
+        let inverse_of_unit = match known.get(&unit) {
+            Some(&x) => x,
+            None => match inverse(unit, m) {
+                Some(x) => {
+                    known.insert(x, unit);
+                    x
+                }
+                None => continue,
+            },
+        };
+        pairs.push((unit, inverse_of_unit));
+    }
+
+    pairs
+}
+
+//dev:      This function checks a batch of claimed inverses. Each claim (a, x, m) says that x is the inverse of a
+//          under Mod m, and checking it only takes one multiplication, which is far cheaper than calculating it.
+//          x doesn't have to be reduced: any x with a * x ≅ 1 (mod m) is accepted.
+//returns:  Returns one entry per claim, in the same order: true if the claim holds, false otherwise (including when m is 0).
+pub fn batch_verify(claims: &[(u64, u64, u64)]) -> Vec<bool> {
+    claims.iter().map(|&(a, x, m)| m != 0 && mod_mul(a, x, m) == 1 % m).collect()
+}
+
+//returns:  Returns true if every claim in the batch holds, stopping at the first one that doesn't.
+pub fn all_valid(claims: &[(u64, u64, u64)]) -> bool {
+    claims.iter().all(|&(a, x, m)| m != 0 && mod_mul(a, x, m) == 1 % m)
+}
+
+//dev:      This function calculates the inverse of every a in start..end under Mod m, working through the range
+//          chunk values at a time. Right now the chunks run one after the other, but each is handled by a single
+//          call on a contiguous block, which is the shape a SIMD or multithreaded version would split the work into.
+//          The chunk size only changes the internal batching, never the result. Panics if chunk is 0.
+//returns:  Returns one entry per a, in order: Some(inverse) or None if a has no inverse. Empty if start >= end.
+pub fn inverse_range_chunked(start: u64, end: u64, m: u64, chunk: usize) -> Vec<Option<u64>> {
+    assert!(chunk > 0, "the chunk size must be greater than 0");
+
+    let total = end.saturating_sub(start);
+    let mut inverses = Vec::with_capacity(total as usize);
+    let mut chunk_start = start;
+
+    while chunk_start < end {
+        let chunk_end = chunk_start.saturating_add(chunk as u64).min(end);
+        inverse_chunk(chunk_start..chunk_end, m, &mut inverses);
+        chunk_start = chunk_end;
+    }
+
+    inverses
+}
+
+//dev:      The work done for one chunk of inverse_range_chunked, appending its results to out.
+fn inverse_chunk(chunk: Range<u64>, m: u64, out: &mut Vec<Option<u64>>) {
+    out.extend(chunk.map(|a| inverse(a, m)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parallel_matches_sequential() {
+        let moduli: Vec<u64> = (0..5000).map(|i| 1_000_000_007 - 2 * i).chain(0..100).collect();
+        for a in [0, 1, 2, 12345, u64::MAX] {
+            assert_eq!(inverse_across_moduli_parallel(a, &moduli), inverse_across_moduli(a, &moduli));
+        }
+        assert_eq!(inverse_across_moduli_parallel(3, &[7, 10, 0, 1]), vec![Some(5), Some(7), None, Some(0)]);
+        assert!(inverse_across_moduli_parallel(3, &[]).is_empty());
+    }
+
+    #[test]
+    fn running_inverse_undoes_each_prefix() {
+        let values = [3, 5, 6, 10, 12];
+        let m = 17;
+        let inverses = running_inverse(&values, m).unwrap();
+        let mut product = 1;
+        for (i, &value) in values.iter().enumerate() {
+            product = mod_mul(product, value, m);
+            assert_eq!(mod_mul(inverses[i], product, m), 1);
+        }
+        assert_eq!(running_inverse(&[3, 4, 5], 10), None);
+        assert_eq!(running_inverse(&[], 7), Some(Vec::new()));
+    }
+
+    #[test]
+    fn batch_report_counts_the_failures() {
+        let pairs = [(3, 7), (4, 10), (5, 11), (6, 9), (0, 1), (2, 0)];
+        let (inverses, failures) = batch_inverse_with_report(&pairs);
+        assert_eq!(inverses, vec![Some(5), None, Some(9), None, Some(0), None]);
+        assert_eq!(failures, 3);
+        assert_eq!(failures, inverses.iter().filter(|x| x.is_none()).count());
+        assert_eq!(batch_inverse_with_report(&[]), (Vec::new(), 0));
+    }
+
+    #[test]
+    fn pair_unit_inverses_pairs_both_ways() {
+        let m = 35;
+        let units = crate::units_mod(m);
+        let pairs = pair_unit_inverses(&units, m);
+        assert_eq!(pairs.len(), units.len());
+        for &(a, b) in &pairs {
+            assert_eq!(mod_mul(a, b, m), 1);
+            assert!(pairs.contains(&(b, a)), "({}, {}) has no mirror", a, b);
+        }
+        assert_eq!(pair_unit_inverses(&[3, 4, 7], 10), vec![(3, 7), (7, 3)]);
+    }
+
+    #[test]
+    fn prefix_product_inverses_matches_naive_inversion() {
+        let values = [3u64, 5, 6, 10, 12, 16, 1_000_000_006];
+        let m = 1_000_000_007;
+        let mut product = 1;
+        let naive: Vec<u64> = values
+            .iter()
+            .map(|&value| {
+                product = mod_mul(product, value, m);
+                inverse(product, m).unwrap()
+            })
+            .collect();
+        assert_eq!(prefix_product_inverses(&values, m), Some(naive));
+        assert_eq!(prefix_product_inverses(&values, m), running_inverse(&values, m));
+        assert_eq!(prefix_product_inverses(&[3, 4, 5], 10), None);
+        assert_eq!(prefix_product_inverses(&[], 10), Some(Vec::new()));
+        assert_eq!(prefix_product_inverses(&[3], 0), None);
+    }
+
+    #[test]
+    fn batch_verify_checks_each_claim() {
+        let claims = [(3, 5, 7), (3, 4, 7), (10, 12, 17), (2, 0, 4), (4, 3, 0), (5, 1, 1), (3, 12, 7)];
+        assert_eq!(batch_verify(&claims), vec![true, false, true, false, false, true, true]);
+        assert!(!all_valid(&claims));
+        assert!(all_valid(&[(3, 5, 7), (10, 12, 17), (5, 1, 1)]));
+        assert!(all_valid(&[]));
+    }
+
+    #[test]
+    fn chunk_size_never_changes_the_result() {
+        let expected: Vec<Option<u64>> = (5..300).map(|a| inverse(a, 360)).collect();
+        for chunk in [1, 2, 7, 64, 295, 1000] {
+            assert_eq!(inverse_range_chunked(5, 300, 360, chunk), expected);
+        }
+        assert!(inverse_range_chunked(10, 10, 7, 3).is_empty());
+        assert!(inverse_range_chunked(10, 2, 7, 3).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "the chunk size must be greater than 0")]
+    fn zero_chunk_size_panics() {
+        inverse_range_chunked(0, 10, 7, 0);
+    }
+
+    #[test]
+    fn progress_rises_to_the_total() {
+        let pairs: Vec<(u64, u64)> = (0..250).map(|a| (a, 360)).collect();
+        let mut calls = Vec::new();
+        let inverses = batch_inverse_progress(&pairs, |done, total| calls.push((done, total)));
+
+        assert_eq!(inverses, pairs.iter().map(|&(a, m)| inverse(a, m)).collect::<Vec<_>>());
+        assert!(calls.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(calls.iter().all(|&(_, total)| total == 250));
+        assert_eq!(calls.last(), Some(&(250, 250)));
+        assert!(calls.len() > 1);
+
+        let mut calls = Vec::new();
+        assert!(batch_inverse_progress(&[], |done, total| calls.push((done, total))).is_empty());
+        assert_eq!(calls, vec![(0, 0)]);
+    }
+
+    #[test]
+    fn zero_budget_returns_no_results_without_panicking() {
+        let pairs: Vec<(u64, u64)> = (0..100_000).map(|a| (a, 1_000_000_007)).collect();
+        let (inverses, processed) = batch_inverse_budgeted(&pairs, Duration::ZERO);
+        assert_eq!(processed, 0);
+        assert!(inverses.is_empty());
+
+        //whatever fits in a tiny budget is a correct prefix of the batch
+        let (inverses, processed) = batch_inverse_budgeted(&pairs, Duration::from_micros(50));
+        assert_eq!(inverses.len(), processed);
+        assert!(processed <= pairs.len());
+        assert!(inverses.iter().zip(&pairs).all(|(&x, &(a, m))| x == inverse(a, m)));
+    }
+
+    #[test]
+    fn generous_budget_finishes_the_batch() {
+        let pairs = [(3, 7), (4, 10), (2, 1_000_000_007)];
+        let (inverses, processed) = batch_inverse_budgeted(&pairs, Duration::from_secs(60));
+        assert_eq!(processed, 3);
+        assert_eq!(inverses, vec![Some(5), None, Some(500_000_004)]);
+    }
+}
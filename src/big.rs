@@ -0,0 +1,178 @@
+//notice:   The Extended Euclidean Algorithm only needs a handful of integer operations: division with remainder,
+//          subtraction, multiplication and a few comparisons. Any arbitrary-precision integer type that provides
+//          them can run the algorithm, so the inverse is written once against this trait instead of against one library.
+//dev:      The operations a big integer type has to provide to be used with inverse_big.
+//          num-bigint is supported through the bigint feature; rug or ibig would go behind a feature of their own.
+pub trait BigIntBackend: Clone + PartialEq {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn is_zero(&self) -> bool;
+    fn is_negative(&self) -> bool;
+    fn add(&self, other: &Self) -> Self;
+    fn sub(&self, other: &Self) -> Self;
+    fn mul(&self, other: &Self) -> Self;
+    //returns:  Returns the quotient and remainder of self / other, rounding the quotient towards zero
+    fn div_rem(&self, other: &Self) -> (Self, Self);
+}
+
+//dev:      i128 is the widest integer the standard library has, so it doubles as the built-in backend.
+//          It covers every u64 modulus, since the coefficients never grow past the modulus.
+impl BigIntBackend for i128 {
+    fn zero() -> Self {
+        0
+    }
+
+    fn one() -> Self {
+        1
+    }
+
+    fn is_zero(&self) -> bool {
+        *self == 0
+    }
+
+    fn is_negative(&self) -> bool {
+        *self < 0
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        self + other
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        self - other
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        self * other
+    }
+
+    fn div_rem(&self, other: &Self) -> (Self, Self) {
+        (self / other, self % other)
+    }
+}
+
+//dev:      The arbitrary-precision backend, for moduli that don't fit in an i128. Enabled by the bigint feature.
+#[cfg(feature = "bigint")]
+impl BigIntBackend for num_bigint::BigInt {
+    fn zero() -> Self {
+        num_bigint::BigInt::ZERO
+    }
+
+    fn one() -> Self {
+        num_bigint::BigInt::from(1)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.sign() == num_bigint::Sign::NoSign
+    }
+
+    fn is_negative(&self) -> bool {
+        self.sign() == num_bigint::Sign::Minus
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        self + other
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        self - other
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        self * other
+    }
+
+    //BigInt's / and % truncate towards zero, the same as i128
+    fn div_rem(&self, other: &Self) -> (Self, Self) {
+        (self / other, self % other)
+    }
+}
+
+//dev:      This function is the same Extended Euclidean loop as modular_multiplicative_inverse,
+//          written against BigIntBackend so it works with any big integer type.
+//          a may be negative; it is first brought into the range [0, m).
+//returns:  Returns Some(inverse) in the range [0, m), or None if m isn't positive or a and m aren't relatively prime.
+#[allow(non_snake_case)]
+pub fn inverse_big<B: BigIntBackend>(a: &B, m: &B) -> Option<B> {
+    if m.is_zero() || m.is_negative() {
+        return None;
+    }
+
+    //the remainder keeps the sign of a, so a negative remainder is shifted up by m
+    let mut a = a.div_rem(m).1;
+    if a.is_negative() {
+        a = a.add(m);
+    }
+
+    let mut x = B::zero();
+    let mut y = B::one();
+    let mut A = m.clone();
+    let mut B = a;
+
+    while !B.is_zero() {
+        let (q, r) = A.div_rem(&B);
+        let t = x.sub(&y.mul(&q));
+
+        A = B;
+        B = r;
+        x = y;
+        y = t;
+    }
+
+    //when B reaches 0, A holds the gcd. Anything other than 1 means there is no inverse.
+    //Mod 1 the loop never runs and A is 1 already, so the inverse comes out as 0
+    if A != B::one() {
+        return None;
+    }
+
+    if x.is_negative() {
+        x = x.add(m);
+    }
+
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn i128_backend_matches_try_inverse() {
+        assert_eq!(inverse_big(&3i128, &7), Some(5));
+        assert_eq!(inverse_big(&-3i128, &7), Some(2));
+        assert_eq!(inverse_big(&4i128, &10), None);
+        assert_eq!(inverse_big(&5i128, &1), Some(0));
+        assert_eq!(inverse_big(&5i128, &0), None);
+        assert_eq!(inverse_big(&5i128, &-7), None);
+        for m in 2..60u64 {
+            for a in 0..m {
+                assert_eq!(inverse_big(&(a as i128), &(m as i128)), crate::inverse(a, m).map(|x| x as i128));
+            }
+        }
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn bigint_backend_agrees_with_i128() {
+        use num_bigint::BigInt;
+
+        for &(a, m) in &[(3i128, 7i128), (-3, 7), (4, 10), (5, 1), (2, 18446744073709551557), (17, i128::MAX)] {
+            let big = inverse_big(&BigInt::from(a), &BigInt::from(m));
+            assert_eq!(big, inverse_big(&a, &m).map(BigInt::from));
+        }
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn bigint_backend_handles_moduli_past_i128() {
+        use num_bigint::BigInt;
+
+        //2^521 - 1 is a Mersenne prime, far outside what an i128 can hold
+        let m = (BigInt::from(1) << 521u32) - 1;
+        let a = BigInt::from(3) << 300u32;
+        let x = inverse_big(&a, &m).unwrap();
+        assert_eq!(&a * &x % &m, BigInt::from(1));
+        assert!(x < m && x.sign() != num_bigint::Sign::Minus);
+        assert_eq!(inverse_big(&(&m * 2), &(&m * 6)), None);
+    }
+}
@@ -0,0 +1,191 @@
+use crate::number_theory::euler_totient;
+use crate::{inverse, is_relatively_prime, mod_pow};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+//notice:   Euler's theorem: a^φ(m) ≅ 1 (mod m) whenever a and m are relatively prime,
+//          so a^(φ(m) - 1) is the modular multiplicative inverse of a.
+//dev:      Remembers the totients it has calculated, so repeated inverses under the same composite
+//          modulus only factor it once. hits and misses count how often the cache was used.
+#[derive(Debug, Default)]
+pub struct TotientCache {
+    totients: HashMap<u64, u64>,
+    hits: u64,
+    misses: u64,
+}
+
+impl TotientCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    //returns:  Returns φ(n), calculating it only the first time n is seen
+    pub fn totient(&mut self, n: u64) -> u64 {
+        if let Some(&phi) = self.totients.get(&n) {
+            self.hits += 1;
+            return phi;
+        }
+
+        self.misses += 1;
+        let phi = euler_totient(n);
+        self.totients.insert(n, phi);
+        phi
+    }
+
+    //returns:  Returns the inverse of a under Mod m as a^(φ(m) - 1), or None if it doesn't exist
+    pub fn inverse(&mut self, a: u64, m: u64) -> Option<u64> {
+        if m == 0 || !is_relatively_prime(a, m) {
+            return None;
+        }
+
+        let phi = self.totient(m);
+        Some(mod_pow(a, phi - 1, m))
+    }
+
+    //returns:  Returns how many totient lookups were answered from the cache
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    //returns:  Returns how many totients had to be calculated
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+}
+
+//dev:      Remembers the inverses it has calculated under one fixed modulus. Lookups only need &self,
+//          so a single cache can be put in an Arc and shared by every thread of a server.
+//          Residues without an inverse are remembered too (as None), so asking again doesn't redo the gcd.
+//          hits and misses count how often the cache was used, like in TotientCache.
+#[derive(Debug)]
+pub struct SharedInverseCache {
+    modulus: u64,
+    inverses: Mutex<HashMap<u64, Option<u64>>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl SharedInverseCache {
+    pub fn new(modulus: u64) -> Self {
+        Self {
+            modulus,
+            inverses: Mutex::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    //returns:  Returns the inverse of a under the cache's modulus, or None if it doesn't exist
+    pub fn inverse(&self, a: u64) -> Option<u64> {
+        let a = a % self.modulus.max(1);
+
+        if let Some(&cached) = self.lock().get(&a) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return cached;
+        }
+
+        //the lock isn't held while calculating, so two threads may both work out the same inverse,
+        //which is harmless because they both insert the same value
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let inverse = inverse(a, self.modulus);
+        self.lock().insert(a, inverse);
+        inverse
+    }
+
+    //returns:  Returns how many residues are stored, with or without an inverse
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    //returns:  Returns how many lookups were answered from the cache
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    //returns:  Returns how many inverses had to be calculated
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    //a thread that panicked while holding the lock can't have left a wrong entry behind,
+    //because every insert is a single complete call, so a poisoned lock is still safe to use
+    fn lock(&self) -> MutexGuard<'_, HashMap<u64, Option<u64>>> {
+        self.inverses.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn totient_cache_hits_on_the_second_query() {
+        let mut cache = TotientCache::new();
+        assert_eq!(cache.inverse(7, 360), Some(103));
+        assert_eq!((cache.hits(), cache.misses()), (0, 1));
+        assert_eq!(cache.inverse(11, 360), crate::inverse(11, 360));
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+        assert_eq!(cache.totient(360), 96);
+        assert_eq!(cache.hits(), 2);
+
+        //no inverse means no totient lookup at all
+        assert_eq!(cache.inverse(6, 360), None);
+        assert_eq!(cache.inverse(6, 0), None);
+        assert_eq!((cache.hits(), cache.misses()), (2, 1));
+    }
+
+    #[test]
+    fn shared_cache_is_consistent_across_threads() {
+        let cache = Arc::new(SharedInverseCache::new(1_000_000_007));
+        let handles: Vec<_> = (0..4u64)
+            .map(|t| {
+                let cache = Arc::clone(&cache);
+                //each thread covers 0..300 plus a stretch of its own, so most residues are asked for several times
+                thread::spawn(move || (0..300).chain(1000 * t..1000 * t + 100).map(|a| (a, cache.inverse(a))).collect::<Vec<_>>())
+            })
+            .collect();
+
+        for handle in handles {
+            for (a, x) in handle.join().unwrap() {
+                assert_eq!(x, inverse(a, 1_000_000_007));
+            }
+        }
+
+        let lookups = 4 * 400;
+        assert_eq!(cache.hits() + cache.misses(), lookups);
+        //0..100 overlaps with thread 0's own stretch, leaving 300 + 3 * 100 distinct residues
+        assert_eq!(cache.len(), 600);
+        assert!(cache.hits() > 0);
+
+        //everything is cached now, so a second round only hits
+        let misses = cache.misses();
+        assert_eq!(cache.inverse(123), inverse(123, 1_000_000_007));
+        assert_eq!(cache.misses(), misses);
+    }
+
+    #[test]
+    fn shared_cache_remembers_missing_inverses() {
+        let cache = SharedInverseCache::new(12);
+        assert_eq!(cache.inverse(8), None);
+        assert_eq!((cache.hits(), cache.misses()), (0, 1));
+        assert_eq!(cache.inverse(8), None);
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+
+        //20 is 8 Mod 12, so it is the same cache entry
+        assert_eq!(cache.inverse(20), None);
+        assert_eq!(cache.inverse(5), Some(5));
+        assert_eq!((cache.hits(), cache.misses()), (2, 2));
+        assert_eq!(cache.len(), 2);
+    }
+}
@@ -0,0 +1,122 @@
+use crate::extended_gcd;
+
+//notice:   ax + by = c has integer solutions exactly when g = GCD(a, b) divides c.
+//          Extended Euclid gives ax0 + by0 = g, and multiplying both sides by c / g gives a solution for c.
+//          With a = 3, b = 5, c = 1 this is the same calculation as the inverse of 3 Mod 5.
+//dev:      This function finds one particular solution (x, y) of ax + by = c.
+//returns:  Returns Some((x, y)), or None if there is no solution or the solution doesn't fit in an i64.
+pub fn solve_diophantine(a: i64, b: i64, c: i64) -> Option<(i64, i64)> {
+    //0x + 0y is always 0
+    if a == 0 && b == 0 {
+        return if c == 0 { Some((0, 0)) } else { None };
+    }
+
+    let (x, y, g) = extended_gcd(a, b);
+    if c % g != 0 {
+        return None;
+    }
+
+    let scale = (c / g) as i128;
+    let x = i64::try_from(x as i128 * scale).ok()?;
+    let y = i64::try_from(y as i128 * scale).ok()?;
+
+    Some((x, y))
+}
+
+//notice:   If (x0, y0) solves ax + by = c and g = GCD(a, b), then so does (x0 + k * b/g, y0 - k * a/g) for every integer k,
+//          because the extra terms a * k * b/g and -b * k * a/g cancel out. Every solution has this form.
+//dev:      This function lists that family of solutions for k from -k_max to k_max.
+//          Members that would overflow an i64 are skipped.
+//          When a and b are both 0 there is no single family to list (every pair solves 0 = 0), so None is returned.
+//returns:  Returns an iterator over the solutions in increasing order of k, or None if there are none.
+pub fn diophantine_solutions(a: i64, b: i64, c: i64, k_max: i64) -> Option<impl Iterator<Item = (i64, i64)>> {
+    if a == 0 && b == 0 {
+        return None;
+    }
+
+    let (x0, y0) = solve_diophantine(a, b, c)?;
+    let (_, _, g) = extended_gcd(a, b);
+    let (step_x, step_y) = (b / g, a / g);
+
+    Some((-k_max..=k_max).filter_map(move |k| {
+        let x = k.checked_mul(step_x)?.checked_add(x0)?;
+        let y = y0.checked_sub(k.checked_mul(step_y)?)?;
+        Some((x, y))
+    }))
+}
+
+//dev:      This function picks the solution of ax + by = c with the smallest x that isn't negative, as needed for
+//          counting problems such as making an amount out of two coin sizes.
+//          Solutions are spaced |b/g| apart in x, so the smallest x is x0 reduced under Mod |b/g|.
+//          When a or b is 0, the matching variable can be anything and 0 is used for it.
+//returns:  Returns Some((x, y)) if that solution also has y >= 0, None otherwise.
+pub fn diophantine_smallest_nonneg(a: i64, b: i64, c: i64) -> Option<(u64, u64)> {
+    let (x0, _) = solve_diophantine(a, b, c)?;
+
+    let (x, y) = if b == 0 {
+        //x = c / a (exact, since a solution exists). If a is 0 as well, c is 0 and (0, 0) works
+        if a == 0 { (0, 0) } else { (c as i128 / a as i128, 0) }
+    } else {
+        let (_, _, g) = extended_gcd(a, b);
+        let step = (b as i128 / g as i128).abs();
+        let x = (x0 as i128).rem_euclid(step);
+        //ax + by = c, so y = (c - ax) / b, which divides exactly because x is a solution
+        (x, (c as i128 - a as i128 * x) / b as i128)
+    };
+
+    if x < 0 || y < 0 {
+        return None;
+    }
+
+    Some((u64::try_from(x).ok()?, u64::try_from(y).ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solve_diophantine_solvable_and_not() {
+        let (x, y) = solve_diophantine(3, 5, 1).unwrap();
+        assert_eq!(3 * x + 5 * y, 1);
+        //x is the inverse of 3 Mod 5, up to a multiple of 5
+        assert_eq!(x.rem_euclid(5), 2);
+
+        assert_eq!(solve_diophantine(2, 4, 3), None);
+        let (x, y) = solve_diophantine(6, 15, 9).unwrap();
+        assert_eq!(6 * x + 15 * y, 9);
+        assert_eq!(solve_diophantine(0, 0, 0), Some((0, 0)));
+        assert_eq!(solve_diophantine(0, 0, 1), None);
+        //x0 = 2 scaled by i64::MAX doesn't fit in an i64
+        assert_eq!(solve_diophantine(3, 5, i64::MAX), None);
+    }
+
+    #[test]
+    fn diophantine_solutions_all_satisfy_the_equation() {
+        let solutions: Vec<(i64, i64)> = diophantine_solutions(6, 15, 9, 5).unwrap().collect();
+        assert_eq!(solutions.len(), 11);
+        for &(x, y) in &solutions {
+            assert_eq!(6 * x + 15 * y, 9);
+        }
+        //consecutive members are b/g = 5 apart in x and a/g = 2 apart in y
+        assert_eq!(solutions[1].0 - solutions[0].0, 5);
+        assert_eq!(solutions[0].1 - solutions[1].1, 2);
+
+        assert!(diophantine_solutions(2, 4, 3, 5).is_none());
+        assert!(diophantine_solutions(0, 0, 0, 5).is_none());
+        //members past i64::MAX are skipped instead of wrapping
+        assert!(diophantine_solutions(1, i64::MAX, 0, 3).unwrap().all(|(x, y)| x as i128 + y as i128 * i64::MAX as i128 == 0));
+    }
+
+    #[test]
+    fn diophantine_smallest_nonneg_picks_the_smallest_x() {
+        //making 17 out of coins of 3 and 5: 4 * 3 + 1 * 5
+        assert_eq!(diophantine_smallest_nonneg(3, 5, 17), Some((4, 1)));
+        //7 can't be made out of 3 and 5
+        assert_eq!(diophantine_smallest_nonneg(3, 5, 7), None);
+        assert_eq!(diophantine_smallest_nonneg(2, 4, 3), None);
+        assert_eq!(diophantine_smallest_nonneg(3, 0, 12), Some((4, 0)));
+        assert_eq!(diophantine_smallest_nonneg(0, 0, 0), Some((0, 0)));
+        assert_eq!(diophantine_smallest_nonneg(-3, 5, 1), Some((3, 2)));
+    }
+}
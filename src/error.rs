@@ -0,0 +1,76 @@
+use crate::parse::ParseError;
+use std::error::Error;
+use std::fmt;
+
+//dev:      The recoverable errors that can happen while calculating a modular multiplicative inverse.
+//          modular_multiplicative_inverse panics on these, the functions that return a Result hand them back instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InverseError {
+    //The modulus is 0, so there is no ring of remainders to work in
+    ZeroModulus,
+    //a and m share a factor bigger than 1, so a has no inverse under Mod m
+    NotRelativelyPrime { a: u64, m: u64, gcd: u64 },
+    //The caller supplied a gcd that doesn't match the one calculated from a and m
+    GcdMismatch { claimed: u64, actual: u64 },
+    //The Euclidean loop ran for more iterations than Lamé's theorem allows, which can only happen if it is broken
+    IterationLimitExceeded { limit: u32 },
+    //One of the numbers given as text couldn't be parsed. The ParseError is kept as the source of this error
+    Parse { input: String, source: ParseError },
+    //The characteristic of a field has to be prime, otherwise the integers under it don't form a field
+    NotAField { characteristic: u64 },
+}
+
+impl fmt::Display for InverseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InverseError::ZeroModulus => write!(f, "the modulus must be greater than 0"),
+            InverseError::NotRelativelyPrime { a, m, gcd } => {
+                write!(f, "{} and {} aren't relatively prime (gcd is {})", a, m, gcd)
+            }
+            InverseError::GcdMismatch { claimed, actual } => {
+                write!(f, "the claimed gcd {} doesn't match the actual gcd {}", claimed, actual)
+            }
+            InverseError::IterationLimitExceeded { limit } => {
+                write!(f, "the Euclidean loop didn't finish within {} iterations", limit)
+            }
+            InverseError::Parse { input, .. } => write!(f, "couldn't parse '{}' as a number", input),
+            InverseError::NotAField { characteristic } => {
+                write!(f, "{} isn't prime, so the integers under it don't form a field", characteristic)
+            }
+        }
+    }
+}
+
+//dev:      source() hands out the underlying error, so tools like anyhow can print the whole chain.
+impl Error for InverseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InverseError::Parse { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_error_is_reachable_through_source() {
+        let error = crate::inverse_str("3", "1e9+").unwrap_err();
+        assert_eq!(error.to_string(), "couldn't parse '1e9+' as a number");
+
+        let source = error.source().expect("a parse error has a source");
+        assert_eq!(source.downcast_ref::<ParseError>(), Some(&ParseError::Unexpected { position: 4, found: None }));
+        assert_eq!(source.to_string(), "the expression ends early at position 4");
+        assert!(source.source().is_none());
+    }
+
+    #[test]
+    fn inverse_str_parses_both_numbers() {
+        assert_eq!(crate::inverse_str("3", "7"), Ok(5));
+        assert_eq!(crate::inverse_str("2", "1e9+7"), Ok(500_000_004));
+        assert_eq!(crate::inverse_str("4", "10"), Err(InverseError::NotRelativelyPrime { a: 4, m: 10, gcd: 2 }));
+        assert!(InverseError::ZeroModulus.source().is_none());
+    }
+}
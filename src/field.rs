@@ -0,0 +1,133 @@
+use crate::number_theory::is_prime;
+use crate::{inverse, mod_pow, try_inverse, InverseError};
+
+//dev:      The integers Mod some modulus, behind one interface. The trait is object safe, so rings with different
+//          moduli (and different ways of inverting) can be kept together as Box<dyn ModularField>.
+pub trait ModularField {
+    fn modulus(&self) -> u64;
+
+    //returns:  Returns the inverse of a under the modulus, or None if it doesn't exist
+    fn inverse(&self, a: u64) -> Option<u64>;
+}
+
+//dev:      The integers Mod a prime p. Every nonzero element has an inverse, found with Fermat's little theorem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrimeField {
+    p: u64,
+}
+
+impl PrimeField {
+    //dev:      Panics if p isn't prime, because then it isn't a field.
+    pub fn new(p: u64) -> Self {
+        if !is_prime(p) {
+            panic!("the modulus of a PrimeField must be prime, {} isn't", p);
+        }
+
+        PrimeField { p }
+    }
+}
+
+impl ModularField for PrimeField {
+    fn modulus(&self) -> u64 {
+        self.p
+    }
+
+    //a^(p-2) is the inverse of a, since a^(p-1) ≅ 1 (mod p)
+    fn inverse(&self, a: u64) -> Option<u64> {
+        if a.is_multiple_of(self.p) {
+            return None;
+        }
+
+        Some(mod_pow(a, self.p - 2, self.p))
+    }
+}
+
+//dev:      The integers Mod any n > 0. Only the units have an inverse, found with the Extended Euclidean Algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompositeRing {
+    n: u64,
+}
+
+impl CompositeRing {
+    //dev:      Panics if n is 0, because there are no remainders to work with.
+    pub fn new(n: u64) -> Self {
+        if n == 0 {
+            panic!("the modulus of a CompositeRing must be greater than 0");
+        }
+
+        CompositeRing { n }
+    }
+}
+
+impl ModularField for CompositeRing {
+    fn modulus(&self) -> u64 {
+        self.n
+    }
+
+    fn inverse(&self, a: u64) -> Option<u64> {
+        inverse(a, self.n)
+    }
+}
+
+//notice:   The integers Mod n form a field exactly when n is prime. Then every nonzero element has an inverse,
+//          while for a composite n the factors of n (e.g. 2 Mod 6) never have one.
+//dev:      This function calculates the inverse of a in the field with the given characteristic. The characteristic
+//          is checked first, so a composite one is reported as InverseError::NotAField whatever a is,
+//          instead of only failing for the unlucky values of a.
+//returns:  Returns the inverse of a, InverseError::NotAField if characteristic isn't prime,
+//          or InverseError::NotRelativelyPrime if a is 0 in the field.
+pub fn field_inverse(a: u64, characteristic: u64) -> Result<u64, InverseError> {
+    if !is_prime(characteristic) {
+        return Err(InverseError::NotAField { characteristic });
+    }
+
+    try_inverse(a, characteristic)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inverts_through_dynamic_dispatch() {
+        let rings: Vec<Box<dyn ModularField>> =
+            vec![Box::new(PrimeField::new(7)), Box::new(CompositeRing::new(10)), Box::new(PrimeField::new(1_000_000_007))];
+
+        assert_eq!(rings.iter().map(|r| r.modulus()).collect::<Vec<_>>(), vec![7, 10, 1_000_000_007]);
+        assert_eq!(rings.iter().map(|r| r.inverse(3)).collect::<Vec<_>>(), vec![Some(5), Some(7), Some(333_333_336)]);
+        assert_eq!(rings.iter().map(|r| r.inverse(14)).collect::<Vec<_>>(), vec![None, None, inverse(14, 1_000_000_007)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "the modulus of a PrimeField must be prime, 12 isn't")]
+    fn prime_field_rejects_a_composite_modulus() {
+        PrimeField::new(12);
+    }
+
+    #[test]
+    #[should_panic(expected = "the modulus of a CompositeRing must be greater than 0")]
+    fn composite_ring_rejects_0() {
+        CompositeRing::new(0);
+    }
+
+    #[test]
+    fn every_nonzero_element_of_a_prime_field_has_an_inverse() {
+        for p in [2, 3, 13, 101] {
+            for a in 1..p {
+                let x = field_inverse(a, p).unwrap();
+                assert_eq!(a * x % p, 1);
+            }
+            assert!(matches!(field_inverse(0, p), Err(InverseError::NotRelativelyPrime { .. })));
+            assert!(matches!(field_inverse(p, p), Err(InverseError::NotRelativelyPrime { .. })));
+        }
+    }
+
+    #[test]
+    fn a_composite_characteristic_is_not_a_field_whatever_a_is() {
+        for characteristic in [0, 1, 4, 12, 561] {
+            for a in [0, 1, 5, 7] {
+                assert_eq!(field_inverse(a, characteristic), Err(InverseError::NotAField { characteristic }));
+            }
+        }
+    }
+}
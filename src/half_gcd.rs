@@ -0,0 +1,242 @@
+use num_bigint::{BigInt, Sign};
+use std::mem;
+
+//notice:   The Euclidean Algorithm takes a quotient per row, and for n-bit inputs there are about n rows with
+//          n-bit numbers in them, so it is quadratic. Most quotients only depend on the leading bits though:
+//          the rows for the top half of a and b are the same as the first rows for a and b themselves.
+//          The half-GCD works them out from the top halves recursively, collects them in a 2x2 matrix and applies
+//          that matrix to the full numbers in one go. With num-bigint's Karatsuba multiplication this beats the
+//          row-by-row loop once the inputs are a few thousand bits long.
+//dev:      Below this many bits the recursion isn't worth it and the rows are taken one by one.
+const BASE_BITS: u64 = 1024;
+
+//dev:      [[m00, m01], [m10, m11]] with (a, b) = M * (α, β), where (α, β) is the row the matrix has reduced (a, b) to.
+type Matrix = [[BigInt; 2]; 2];
+
+fn identity() -> Matrix {
+    [[BigInt::from(1), BigInt::ZERO], [BigInt::ZERO, BigInt::from(1)]]
+}
+
+fn is_zero(n: &BigInt) -> bool {
+    n.sign() == Sign::NoSign
+}
+
+//dev:      Takes one Euclidean row, (a, b) -> (b, a - qb), and appends its matrix [[q, 1], [1, 0]] to m.
+fn euclid_step(m: &mut Matrix, a: &mut BigInt, b: &mut BigInt) {
+    let q = &*a / &*b;
+    let r = &*a - &q * &*b;
+    *a = mem::replace(b, r);
+
+    for row in m.iter_mut() {
+        let next = &row[0] * &q + &row[1];
+        row[1] = mem::replace(&mut row[0], next);
+    }
+}
+
+fn mul(m: &Matrix, n: &Matrix) -> Matrix {
+    [
+        [&m[0][0] * &n[0][0] + &m[0][1] * &n[1][0], &m[0][0] * &n[0][1] + &m[0][1] * &n[1][1]],
+        [&m[1][0] * &n[0][0] + &m[1][1] * &n[1][0], &m[1][0] * &n[0][1] + &m[1][1] * &n[1][1]],
+    ]
+}
+
+//dev:      Works out (α, β) = M⁻¹ * (a, b). M is a product of row matrices, so its determinant is ±1
+//          and the inverse is ±[[m11, -m01], [-m10, m00]].
+//returns:  Returns the reduced row, or None if it isn't a valid Euclidean row (α > β ≥ 0) for the full numbers.
+fn apply_inverse(m: &Matrix, a: &BigInt, b: &BigInt) -> Option<(BigInt, BigInt)> {
+    let det = &m[0][0] * &m[1][1] - &m[0][1] * &m[1][0];
+    let alpha = (&m[1][1] * a - &m[0][1] * b) * &det;
+    let beta = (&m[0][0] * b - &m[1][0] * a) * &det;
+
+    if beta.sign() == Sign::Minus || alpha <= beta {
+        return None;
+    }
+
+    Some((alpha, beta))
+}
+
+//dev:      The half-GCD step. For a > b ≥ 0 with a n bits long, this function reduces (a, b) until b is at most
+//          n / 2 + 1 bits long. It recurses on the top halves twice, first to get from n to about 3n / 4 bits
+//          and then from there down to n / 2. Any rows the recursion misses are taken one by one at the end,
+//          so the result is a correct Euclidean row even when the top halves lead the recursion astray.
+//returns:  Returns (M, α, β) with (a, b) = M * (α, β).
+fn hgcd(a: BigInt, b: BigInt) -> (Matrix, BigInt, BigInt) {
+    let n = a.bits();
+    let s = n / 2 + 1;
+    let mut m = identity();
+    let (mut a, mut b) = (a, b);
+
+    if b.bits() > s && n > BASE_BITS {
+        let p = n / 2;
+        let (m1, _, _) = hgcd(&a >> p, &b >> p);
+        if let Some((alpha, beta)) = apply_inverse(&m1, &a, &b) {
+            (m, a, b) = (m1, alpha, beta);
+        }
+
+        if b.bits() > s {
+            euclid_step(&mut m, &mut a, &mut b);
+        }
+
+        if b.bits() > s {
+            //a has about 3n / 4 bits now. Dropping p2 bits leaves top halves whose own half-GCD lands near s bits
+            let p2 = (2 * s).saturating_sub(a.bits() + 2).max(1);
+            let (m2, _, _) = hgcd(&a >> p2, &b >> p2);
+            if let Some((alpha, beta)) = apply_inverse(&m2, &a, &b) {
+                (m, a, b) = (mul(&m, &m2), alpha, beta);
+            }
+        }
+    }
+
+    while b.bits() > s {
+        euclid_step(&mut m, &mut a, &mut b);
+    }
+
+    (m, a, b)
+}
+
+//dev:      This function is the Extended Euclidean Algorithm for num-bigint's BigInt, computed with the half-GCD
+//          instead of one row at a time. Enabled by the bigint feature.
+//returns:  Returns (x, y, g) with ax + by = g, where g = GCD(a, b) is never negative.
+pub fn extended_gcd_half(a: &BigInt, b: &BigInt) -> (BigInt, BigInt, BigInt) {
+    let swapped = a.magnitude() < b.magnitude();
+    let (mut alpha, mut beta) = if swapped {
+        (BigInt::from(b.magnitude().clone()), BigInt::from(a.magnitude().clone()))
+    } else {
+        (BigInt::from(a.magnitude().clone()), BigInt::from(b.magnitude().clone()))
+    };
+
+    let mut m = identity();
+    while !is_zero(&beta) {
+        if beta.bits() <= BASE_BITS {
+            euclid_step(&mut m, &mut alpha, &mut beta);
+            continue;
+        }
+
+        let (m1, next_alpha, next_beta) = hgcd(alpha, beta);
+        (m, alpha, beta) = (mul(&m, &m1), next_alpha, next_beta);
+
+        //hgcd stops when beta is half as long as alpha. One more row keeps the loop moving if it already was
+        if !is_zero(&beta) {
+            euclid_step(&mut m, &mut alpha, &mut beta);
+        }
+    }
+
+    //(|a|, |b|) = M * (g, 0), so g = det * (m11 |a| - m01 |b|)
+    let det = &m[0][0] * &m[1][1] - &m[0][1] * &m[1][0];
+    let mut x = &m[1][1] * &det;
+    let mut y = -&m[0][1] * &det;
+    if swapped {
+        mem::swap(&mut x, &mut y);
+    }
+    if a.sign() == Sign::Minus {
+        x = -x;
+    }
+    if b.sign() == Sign::Minus {
+        y = -y;
+    }
+
+    (x, y, alpha)
+}
+
+//dev:      This function calculates the inverse of a under Mod m for BigInt, with the gcd done by extended_gcd_half.
+//          Moduli up to BASE_BITS long never reach the recursion, so for them this is the plain Euclidean loop.
+//returns:  Returns Some(inverse) in the range [0, m), or None if m isn't positive or a and m aren't relatively prime.
+pub fn inverse_big_fast(a: &BigInt, m: &BigInt) -> Option<BigInt> {
+    if m.sign() != Sign::Plus {
+        return None;
+    }
+
+    let (x, _, g) = extended_gcd_half(a, m);
+    if g != BigInt::from(1) {
+        return None;
+    }
+
+    let x = x % m;
+    Some(if x.sign() == Sign::Minus { x + m } else { x })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //a SplitMix64 step, which is plenty to spread the test inputs out
+    fn next(state: &mut u64) -> u64 {
+        *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = *state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn random_big(state: &mut u64, bits: u64) -> BigInt {
+        let words: Vec<u32> = (0..bits.div_ceil(32)).map(|_| next(state) as u32).collect();
+        BigInt::from_slice(Sign::Plus, &words)
+    }
+
+    //the straightforward Extended Euclidean Algorithm, one quotient per row, to check the half-GCD against
+    fn extended_gcd_big(a: &BigInt, b: &BigInt) -> (BigInt, BigInt, BigInt) {
+        let (mut old_r, mut r) = (a.clone(), b.clone());
+        let (mut old_x, mut x) = (BigInt::from(1), BigInt::ZERO);
+        let (mut old_y, mut y) = (BigInt::ZERO, BigInt::from(1));
+
+        while !is_zero(&r) {
+            let q = &old_r / &r;
+            let next_r = &old_r - &q * &r;
+            old_r = mem::replace(&mut r, next_r);
+            let next_x = &old_x - &q * &x;
+            old_x = mem::replace(&mut x, next_x);
+            let next_y = &old_y - &q * &y;
+            old_y = mem::replace(&mut y, next_y);
+        }
+
+        //the gcd can come out negative when a or b is negative, flipping every sign fixes that
+        if old_r.sign() == Sign::Minus {
+            (old_x, old_y, old_r) = (-old_x, -old_y, -old_r);
+        }
+
+        (old_x, old_y, old_r)
+    }
+
+    #[test]
+    fn agrees_with_the_plain_extended_gcd_on_small_inputs() {
+        for &(a, b) in &[(240i64, 46i64), (46, 240), (17, 0), (0, 17), (0, 0), (1, 1), (-240, 46), (240, -46)] {
+            let (a, b) = (BigInt::from(a), BigInt::from(b));
+            assert_eq!(extended_gcd_half(&a, &b), extended_gcd_big(&a, &b));
+        }
+    }
+
+    #[test]
+    fn agrees_with_the_plain_extended_gcd_on_large_inputs() {
+        let mut state = 202;
+        for &bits in &[1500u64, 4000, 20000] {
+            let shared = random_big(&mut state, 64);
+            let a = random_big(&mut state, bits) * &shared;
+            let b = random_big(&mut state, bits - 100) * &shared;
+
+            let half = extended_gcd_half(&a, &b);
+            let plain = extended_gcd_big(&a, &b);
+            assert_eq!(half.2, plain.2);
+            assert_eq!(&a * &half.0 + &b * &half.1, half.2);
+            //the quotient sequence is the same, so the coefficients are the same ones the plain loop finds
+            assert_eq!(half, plain);
+        }
+    }
+
+    #[test]
+    fn inverse_big_fast_matches_the_plain_inverse() {
+        let mut state = 7;
+        //2^4423 - 1 is a Mersenne prime
+        let m = (BigInt::from(1) << 4423u32) - 1;
+        for _ in 0..3 {
+            let a = random_big(&mut state, 5000);
+            let x = inverse_big_fast(&a, &m).unwrap();
+            let (plain_x, _, _) = extended_gcd_big(&a, &m);
+            assert_eq!(x, ((plain_x % &m) + &m) % &m);
+            assert_eq!(&a * &x % &m, BigInt::from(1));
+        }
+
+        assert_eq!(inverse_big_fast(&(&m * 3), &(&m * 5)), None);
+        assert_eq!(inverse_big_fast(&BigInt::from(3), &BigInt::from(7)), Some(BigInt::from(5)));
+        assert_eq!(inverse_big_fast(&BigInt::from(3), &BigInt::ZERO), None);
+    }
+}
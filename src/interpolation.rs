@@ -0,0 +1,80 @@
+use crate::{inverse, mod_add, mod_mul, mod_sub};
+
+//notice:   Lagrange and Newton interpolation divide by the differences x_i - x_j between the nodes,
+//          so under Mod p every difference needs an inverse. For a prime p that means no two nodes may be equal Mod p.
+//dev:          This function works out all of those inverses up front. The diagonal (i = j) has no difference and is 0.
+//Assumption:   Assumes p is prime
+//returns:      Returns a table where entry [i][j] is the inverse of (x_i - x_j) Mod p, or None if two nodes collide Mod p.
+pub fn interpolation_inverses(nodes: &[u64], p: u64) -> Option<Vec<Vec<u64>>> {
+    let n = nodes.len();
+    let mut table = vec![vec![0; n]; n];
+
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let inverse_ij = inverse(mod_sub(nodes[i], nodes[j], p), p)?;
+            table[i][j] = inverse_ij;
+            //x_j - x_i = -(x_i - x_j), so its inverse is the negated inverse
+            table[j][i] = mod_sub(0, inverse_ij, p);
+        }
+    }
+
+    Some(table)
+}
+
+//notice:   Through n points with different nodes there is exactly one polynomial of degree below n. Lagrange's formula
+//          gives its value at x as the sum over i of y_i * Π_{j≠i} (x - x_j) / (x_i - x_j).
+//dev:          This function evaluates that formula under Mod p, taking the divisions from interpolation_inverses.
+//Assumption:   Assumes p is prime
+//returns:      Returns the value of the interpolating polynomial at x, or None if two nodes are equal Mod p.
+pub fn lagrange_interpolate(points: &[(u64, u64)], x: u64, p: u64) -> Option<u64> {
+    let nodes: Vec<u64> = points.iter().map(|&(node, _)| node).collect();
+    let inverses = interpolation_inverses(&nodes, p)?;
+    let mut value = 0;
+
+    for (i, &(_, y)) in points.iter().enumerate() {
+        let mut term = y % p;
+        for (j, &node) in nodes.iter().enumerate() {
+            if i != j {
+                term = mod_mul(term, mod_mul(mod_sub(x, node, p), inverses[i][j], p), p);
+            }
+        }
+        value = mod_add(value, term, p);
+    }
+
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpolation_inverses_for_three_nodes() {
+        let p = 13;
+        let nodes = [2, 5, 11];
+        let table = interpolation_inverses(&nodes, p).unwrap();
+        for i in 0..3 {
+            assert_eq!(table[i][i], 0);
+            for j in (0..3).filter(|&j| j != i) {
+                assert_eq!(mod_mul(mod_sub(nodes[i], nodes[j], p), table[i][j], p), 1, "i = {}, j = {}", i, j);
+            }
+        }
+        //15 and 2 are the same node Mod 13
+        assert_eq!(interpolation_inverses(&[2, 5, 15], p), None);
+        assert_eq!(interpolation_inverses(&[], p), Some(Vec::new()));
+    }
+
+    #[test]
+    fn lagrange_interpolate_recovers_a_polynomial() {
+        let p = 1_000_000_007;
+        //f(x) = 3x^3 + 2x + 7
+        let f = |x: u64| mod_add(mod_add(mod_mul(3, mod_mul(x, mod_mul(x, x, p), p), p), mod_mul(2, x, p), p), 7, p);
+        let points: Vec<(u64, u64)> = [1, 4, 9, 100].iter().map(|&x| (x, f(x))).collect();
+
+        for x in [0, 2, 5, 12345, p - 1] {
+            assert_eq!(lagrange_interpolate(&points, x, p), Some(f(x)), "x = {}", x);
+        }
+        assert_eq!(lagrange_interpolate(&[(1, 5), (1 + p, 6)], 0, p), None);
+        assert_eq!(lagrange_interpolate(&[], 3, p), Some(0));
+    }
+}
@@ -0,0 +1,1160 @@
+#![cfg_attr(feature = "simd", feature(portable_simd))]
+
+pub mod auto;
+pub mod barrett;
+pub mod batch;
+pub mod big;
+pub mod cache;
+pub mod diophantine;
+mod error;
+pub mod field;
+#[cfg(feature = "bigint")]
+pub mod half_gcd;
+pub mod interpolation;
+pub mod linalg;
+pub mod modint;
+pub mod ntt;
+pub mod number_theory;
+pub mod overflow;
+pub mod parse;
+pub mod poly;
+mod rng;
+pub mod rns;
+pub mod shamir;
+#[cfg(feature = "simd")]
+pub mod simd;
+pub mod steps;
+pub mod stream;
+pub mod table;
+pub mod timing;
+
+pub use error::InverseError;
+
+use std::ops::RangeInclusive;
+
+//dev:          This function calculates the modular multiplicative inverse of a number a with respect to Mod b
+//              Rust is a statically typed language, so you must always specify the type for all the parameters in your function
+//returns:      Returns the modular multiplicative inverse
+//              When returning a value in rust, you must specify the return type.
+//A and B are written in capitals to match the table in the documentation, so we silence the snake case lint for them
+#[allow(non_snake_case)]
+pub fn modular_multiplicative_inverse(a: u64, b: u64) -> u64 {
+
+    //The mod multiplicative inverse of A with respect to Mod B is always zero whenever the value of B is 1
+    if b == 1 {
+        return 0;
+    }
+
+    //Checks to see if the two given numbers are relatively prime
+    //Throws an error if they aren't
+    if !is_relatively_prime(a, b) {
+        //There are two types of errors in rust. Recoverable and unrecoverable error.
+        //An unrecoverable error is an error that halts the program whenever an error occurs.
+        //A panic macro is type of unrecoverable error, and that's what we used here.
+        //To end the program whenever the numbers given aren't coprime.
+        panic!("{} and {} aren't relatively prime", a, b);
+    }
+
+    //Under Mod 2 the only number with an inverse is 1 (every odd a), and it is its own inverse since 1 * 1 = 1
+    //We already know a is odd here, because an even a would have failed the check above
+    if b == 2 {
+        return 1;
+    }
+
+    //variables in rust are immutable by default.
+    //Because of the fact that the values of our variables will change during the course of these operations,
+    //we have to make them mutable by adding the "mut" keyword
+    //x, y and t are of type signed integer because of occassions where they become negative
+    let mut x: i64 = 0;
+    let mut y: i64 = 1;
+    let mut t: i64;
+
+    //A, B, q and r can't become negative throughout the lifecycle of the operation.
+    //This is the reason why they are of type unsigned integer.
+    //t, q and r don't need a starting value because they are always assigned inside the loop before they are read
+    let mut A = b;
+    let mut B: u64 = a;
+    let mut q;
+    let mut r: u64;
+
+    //A loop to calculate the multiplicative inverse as long as B(the divisor) isn't zero
+    while B > 0 {
+        q = A/B; //here we calculate the quotient q
+        r = A % B; //calculating the remainder r
+
+        //we had to cast the quotient q to a signed integer. the compiler will throw an error if an operation is carried out on different types.
+        //to cast/convert a type to another type in rust, you use the "as" keyword
+        t = x - y * q as i64;
+
+
+        //this is where the shifting occurs.
+        //A takes the previous value of B.  B takes the previous value of r.  x takes the previous value of y.  y takes the previous value of t.
+        A = B;
+        B = r;
+        x = y;
+        y = t;
+    }
+
+    //if the value of x is below zero, we add it up to 'b' to get a positive value for the multipicative inverse
+    if x < 0 {
+        x += b as i64; //b has to be converted to a signed integer for the compiler not to throw an error.
+    }
+
+    //we expect a positive result (unsigned integer) as our return type. For this reason, x had to be converted to a u64 to be returned correctly.
+    x as u64
+}
+
+//notice:   The x column of the table only matters Mod m, since the inverse is x mod m in the end. Keeping it reduced
+//          on every row means it never goes negative and always fits in a u64, at the cost of a % per row.
+//dev:      This function calculates the inverse with x and y kept in [0, m) instead of as signed numbers.
+//          The product q * y can be as large as m^2, so it is taken in a u128 before it is reduced.
+//returns:  Returns Some(inverse) if a has an inverse under Mod m, None otherwise (including when m is 0).
+pub fn inverse_reduced_coeffs(a: u64, m: u64) -> Option<u64> {
+    if m == 0 {
+        return None;
+    }
+
+    let (mut big, mut small) = (m, a % m);
+    let (mut x, mut y) = (0, 1 % m);
+
+    while small > 0 {
+        let q = big / small;
+        let qy = ((q as u128 * y as u128) % m as u128) as u64;
+        //x - qy Mod m, without going below 0
+        let t = if x >= qy { x - qy } else { x + (m - qy) };
+
+        (big, small) = (small, big % small);
+        (x, y) = (y, t);
+    }
+
+    if big != 1 {
+        return None;
+    }
+
+    Some(x)
+}
+
+//notice:   The coefficients in the table never grow past m in absolute value, but the i64 loop above still runs out
+//          of room once m is past i64::MAX. Forming them in 128 bits leaves room for every u64 modulus.
+//dev:      This function is the loop of modular_multiplicative_inverse with x, y and t widened to i128
+//          (signed, because the coefficients alternate in sign), so y * q is guarded against overflow.
+//returns:  Returns Some(inverse) if a has an inverse under Mod m, None otherwise (including when m is 0).
+pub fn inverse_u128_guarded(a: u64, m: u64) -> Option<u64> {
+    if m == 0 {
+        return None;
+    }
+
+    let (mut big, mut small) = (m, a % m);
+    let (mut x, mut y): (i128, i128) = (0, 1);
+
+    while small > 0 {
+        let q = big / small;
+        let t = x - y * q as i128;
+
+        (big, small) = (small, big % small);
+        (x, y) = (y, t);
+    }
+
+    if big != 1 {
+        return None;
+    }
+
+    Some(x.rem_euclid(m as i128) as u64)
+}
+
+//notice:   There exists a modular multiplicative inverse for a number A under Mod B iff both numbers are relatively prime
+//          or the GCD(Greatest Common Denominator) is 1
+//dev:      This function checks if two numbers are relatively prime.
+//returns:  Returns true if two numbers are relatively prime, false otherwise.
+pub fn is_relatively_prime(a: u64, b: u64) -> bool {
+    //Rust is an expression based language. An expression is a statement that returns a value.
+    //You can return an expression without using the return keyword.
+    //But note, you mustn't make use of semicolon at the end. Adding a semicolon converts the expression to a statement.
+    gcd(a, b) == 1
+}
+
+//dev:          This function calculates the gcd of two numbers
+//Assumption:   Assumes a, b >= 0
+//returns:      Returns the GCD of two integers
+pub fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a //GCD(a, 0) = a
+    } else {
+        gcd(b, a % b) //GCD(a, b) = GCD(b, a mod b)
+    }
+}
+
+//notice:   The Euclidean algorithm works because GCD(A, B) never changes from one row of the table to the next.
+//          Every row (A, B) is replaced by (B, A mod B), and GCD(A, B) = GCD(B, A mod B).
+//dev:      This function walks through the same rows as the inverse loop and checks that the gcd of every
+//          (A, B) pair equals the final gcd, which is the value of A once B becomes 0.
+//returns:  Returns true if the gcd stayed the same on every row, false otherwise.
+pub fn check_gcd_invariant(a: u64, b: u64) -> bool {
+    let mut rows = Vec::new();
+    let (mut big, mut small) = (b, a);
+
+    //we record the gcd of every row before shifting to the next one
+    while small > 0 {
+        rows.push(gcd(big, small));
+        let r = big % small;
+        big = small;
+        small = r;
+    }
+
+    //when the loop ends, B is 0 and A holds the gcd of the original pair
+    rows.iter().all(|&g| g == big)
+}
+
+//notice:   An inverse x of a under Mod m would satisfy ax = 1 + km for some integer k, i.e. ax - km = 1.
+//          The gcd g divides both a and m, so it divides ax - km as well. That means g would have to divide 1,
+//          which only happens when g is 1.
+//dev:      This function explains, in words, why a has no modular multiplicative inverse under Mod m.
+//returns:  Returns None if the inverse exists, otherwise a message describing the obstruction.
+pub fn explain_no_inverse(a: u64, m: u64) -> Option<String> {
+    let g = gcd(a, m);
+
+    if g == 1 {
+        return None;
+    }
+
+    Some(format!(
+        "gcd({}, {}) = {}, so {} has no inverse mod {}; any inverse would require {} | 1, which is impossible.",
+        a, m, g, a, m, g
+    ))
+}
+
+//notice:   The units of a modulus n are the residues in [0, n) that are relatively prime to n.
+//          These are exactly the numbers that have a modular multiplicative inverse under Mod n,
+//          and there are φ(n) of them (Euler's totient).
+//dev:      This function lists the units of n in increasing order.
+//returns:  Returns every residue in [0, n) that has an inverse under Mod n.
+pub fn units_mod(n: u64) -> Vec<u64> {
+    (0..n).filter(|&a| is_relatively_prime(a, n)).collect()
+}
+
+//dev:      This function is the complement of units_mod. It lists the residues that share a factor with n,
+//          which means they have no inverse under Mod n.
+//          The length of this list plus the length of units_mod(n) always adds up to n.
+//returns:  Returns every residue in [0, n) that has no inverse under Mod n.
+pub fn non_units_mod(n: u64) -> Vec<u64> {
+    (0..n).filter(|&a| !is_relatively_prime(a, n)).collect()
+}
+
+//dev:      This function is a non-panicking version of modular_multiplicative_inverse.
+//          Instead of halting the program when a and m aren't relatively prime, it hands the decision back to the caller.
+//          It runs the loop behind try_inverse, whose i128 coefficients also cope with moduli above i64::MAX.
+//returns:  Returns Some(inverse) if a has an inverse under Mod m, None otherwise (including when m is 0).
+pub fn inverse(a: u64, m: u64) -> Option<u64> {
+    try_inverse(a, m).ok()
+}
+
+//dev:          This function multiplies two numbers under Mod m without overflowing.
+//              a * b can be as large as (2^64 - 1)^2, which doesn't fit in a u64, so the product is done in a u128
+//              and only the remainder is converted back.
+//Assumption:   Assumes m > 0
+//returns:      Returns (a * b) mod m
+pub fn mod_mul(a: u64, b: u64, m: u64) -> u64 {
+    ((a as u128 * b as u128) % m as u128) as u64
+}
+
+//dev:          This function adds two numbers under Mod m. Both are reduced first, and the sum is never formed
+//              directly, so it can't overflow even when m is close to u64::MAX.
+//Assumption:   Assumes m > 0
+//returns:      Returns (a + b) mod m
+pub fn mod_add(a: u64, b: u64, m: u64) -> u64 {
+    let (a, b) = (a % m, b % m);
+    if a >= m - b { a - (m - b) } else { a + b }
+}
+
+//dev:          This function subtracts b from a under Mod m, wrapping around instead of going below 0.
+//Assumption:   Assumes m > 0
+//returns:      Returns (a - b) mod m
+pub fn mod_sub(a: u64, b: u64, m: u64) -> u64 {
+    let (a, b) = (a % m, b % m);
+    if a >= b { a - b } else { m - (b - a) }
+}
+
+//notice:   The inverse of a product is the product of the inverses: (ab)^-1 = a^-1 * b^-1 (mod m).
+//dev:      This function inverts (a * b) mod m without ever forming a * b, so it can't overflow.
+//returns:  Returns the inverse of (a * b) under Mod m, or None if either a or b has no inverse.
+pub fn inverse_of_product(a: u64, b: u64, m: u64) -> Option<u64> {
+    let inverse_a = inverse(a, m)?;
+    let inverse_b = inverse(b, m)?;
+
+    Some(mod_mul(inverse_a, inverse_b, m))
+}
+
+//notice:   When dividing 1 by n with long division, every step multiplies the current remainder by 10 under Mod n.
+//          Multiplying by the inverse of 10 walks those remainders backwards, which is how a digit of the repeating
+//          part can be found from the one after it. The inverse only exists when n shares no factor with 10,
+//          which is also exactly when 1/n has a purely repeating decimal expansion.
+//          Example: 1/7 = 0.142857 142857 ... The remainders are 1, 3, 2, 6, 4, 5 and then 1 again.
+//          10 * 5 = 50 = 7 * 7 + 1, so the inverse of 10 Mod 7 is 5, and 3 * 5 = 15 ≅ 1 takes us from the
+//          second remainder back to the first.
+//dev:      This function calculates the inverse of 10 under Mod n.
+//returns:  Returns Some(inverse) if n is relatively prime to 10, None otherwise.
+pub fn inverse_of_ten(n: u64) -> Option<u64> {
+    inverse(10, n)
+}
+
+//dev:      This function calculates the inverse for callers that already worked out gcd(a, m) themselves.
+//          The claimed gcd is checked against the real one first, so a mistake on the caller's side
+//          is reported instead of silently producing a wrong answer.
+//returns:  Returns the inverse of a under Mod m, or an InverseError describing what went wrong.
+pub fn inverse_with_precomputed_gcd(a: u64, m: u64, claimed_gcd: u64) -> Result<u64, InverseError> {
+    if m == 0 {
+        return Err(InverseError::ZeroModulus);
+    }
+
+    let actual = gcd(a, m);
+    if actual != claimed_gcd {
+        return Err(InverseError::GcdMismatch { claimed: claimed_gcd, actual });
+    }
+
+    if actual != 1 {
+        return Err(InverseError::NotRelativelyPrime { a, m, gcd: actual });
+    }
+
+    try_inverse(a, m)
+}
+
+//notice:   Lamé's theorem: the Euclidean loop takes the most steps when A and B are consecutive Fibonacci numbers.
+//          If the loop runs n times starting from A > B > 0, then A is at least the (n + 2)th Fibonacci number.
+//dev:      This function turns that around and finds the largest n whose Fibonacci number still fits under m.
+//returns:  Returns the most iterations the inverse loop can take for any a in [0, m).
+pub fn max_iterations_for_modulus(m: u64) -> u32 {
+    //f and next walk along the Fibonacci numbers F(2), F(3), ...
+    let (mut f, mut next): (u64, u64) = (1, 2);
+    let mut n = 0;
+
+    while next <= m {
+        n += 1;
+        match f.checked_add(next) {
+            Some(sum) => {
+                f = next;
+                next = sum;
+            }
+            None => break,
+        }
+    }
+
+    n
+}
+
+//dev:      This function is a Result-returning version of modular_multiplicative_inverse.
+//          As a guard against the loop never reaching B = 0, it stops once the iterations go past
+//          max_iterations_for_modulus(m) plus a small margin, and reports it instead of hanging.
+//returns:  Returns the inverse of a under Mod m, or an InverseError describing why it couldn't be calculated.
+pub fn try_inverse(a: u64, m: u64) -> Result<u64, InverseError> {
+    try_inverse_with_limit(a, m, max_iterations_for_modulus(m) + 2)
+}
+
+//dev:      The loop behind try_inverse, with the iteration limit passed in.
+//          x and y are i128 so that any u64 modulus can be added back onto them without overflowing.
+#[allow(non_snake_case)]
+fn try_inverse_with_limit(a: u64, m: u64, limit: u32) -> Result<u64, InverseError> {
+    if m == 0 {
+        return Err(InverseError::ZeroModulus);
+    }
+
+    if m == 1 {
+        return Ok(0);
+    }
+
+    //Mod 2 every odd a is 1, its own inverse, and every even a is 0, which has none
+    if m == 2 {
+        return match a % 2 {
+            1 => Ok(1),
+            _ => Err(InverseError::NotRelativelyPrime { a, m, gcd: 2 }),
+        };
+    }
+
+    let mut x: i128 = 0;
+    let mut y: i128 = 1;
+    let mut A = m;
+    let mut B = a % m;
+    let mut iterations = 0;
+
+    while B > 0 {
+        iterations += 1;
+        if iterations > limit {
+            return Err(InverseError::IterationLimitExceeded { limit });
+        }
+
+        let q = A / B;
+        let r = A % B;
+        let t = x - y * q as i128;
+
+        A = B;
+        B = r;
+        x = y;
+        y = t;
+    }
+
+    //A now holds gcd(a, m)
+    if A != 1 {
+        return Err(InverseError::NotRelativelyPrime { a, m, gcd: A });
+    }
+
+    if x < 0 {
+        x += m as i128;
+    }
+
+    Ok(x as u64)
+}
+
+//dev:          This function raises base to the power exp under Mod m using square-and-multiply.
+//              exp is read one bit at a time: every bit squares the running base, and every set bit
+//              multiplies it into the result, so only about 2 * log2(exp) multiplications are needed.
+//Assumption:   Assumes m > 0
+//returns:      Returns base^exp mod m
+pub fn mod_pow(base: u64, exp: u64, m: u64) -> u64 {
+    let mut result = 1 % m;
+    let mut base = base % m;
+    let mut exp = exp;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mod_mul(result, base, m);
+        }
+        base = mod_mul(base, base, m);
+        exp >>= 1;
+    }
+
+    result
+}
+
+//dev:      This function calculates the inverse of a + d under Mod m for a caller who already knows the inverse of a.
+//          Contract: prev_inverse must be the inverse of a under Mod m whenever that inverse exists
+//          (it is ignored otherwise). Debug builds check this.
+//          For now the inverse of a + d is calculated from scratch, so prev_inverse doesn't make it any faster yet,
+//          but callers written against this signature will pick up any cheaper update without changing.
+//          a + d is formed in a u128, so it can't overflow.
+//returns:  Returns the inverse of (a + d) mod m, or None if it doesn't exist.
+pub fn inverse_delta(a: u64, d: u64, m: u64, prev_inverse: u64) -> Option<u64> {
+    debug_assert!(
+        inverse(a, m).is_none_or(|x| x == prev_inverse),
+        "prev_inverse {} is not the inverse of {} Mod {}",
+        prev_inverse,
+        a,
+        m
+    );
+
+    if m == 0 {
+        return None;
+    }
+
+    let shifted = ((a as u128 + d as u128) % m as u128) as u64;
+    inverse(shifted, m)
+}
+
+//notice:   Bézout's theorem: for any integers a and b there are integers x and y with ax + by = GCD(a, b).
+//dev:          This function is the Extended Euclidean Algorithm for signed numbers. It runs the same rows as the table
+//              in the documentation, carrying the coefficients of both a and b instead of only one of them.
+//              The work is done in i128 so the intermediate values can't overflow.
+//Assumption:   Assumes a and b aren't i64::MIN, whose absolute value doesn't fit in an i64
+//returns:      Returns (x, y, g) with ax + by = g, where g = GCD(a, b) is never negative.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a as i128, b as i128);
+    let (mut old_x, mut x) = (1i128, 0i128);
+    let (mut old_y, mut y) = (0i128, 1i128);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    //the gcd can come out negative when a or b is negative, flipping every sign fixes that
+    if old_r < 0 {
+        (old_r, old_x, old_y) = (-old_r, -old_x, -old_y);
+    }
+
+    (old_x as i64, old_y as i64, old_r as i64)
+}
+
+//dev:      This function searches range for the smallest modulus m > 1 under which a has an inverse,
+//          which helps when a modulus has to be picked for a given a.
+//returns:  Returns Some(m) with GCD(a, m) = 1, or None if no modulus in range qualifies.
+pub fn smallest_invertible_modulus(a: u64, range: RangeInclusive<u64>) -> Option<u64> {
+    range.filter(|&m| m > 1).find(|&m| is_relatively_prime(a, m))
+}
+
+//notice:   The quotients q of the Euclidean rows are the partial quotients of the continued fraction of a / b.
+//          Example: 43 / 19 = 2 + 1 / (3 + 1 / (1 + 1 / 4)), and the rows for (43, 19) have quotients 2, 3, 1, 4.
+//dev:          This function is the Extended Euclidean Algorithm starting from the row (a, b),
+//              keeping the quotient of every row instead of throwing it away.
+//Assumption:   Assumes a, b <= i64::MAX, so the coefficients and the gcd fit in an i64
+//returns:      Returns (x, y, g, quotients) with ax + by = g = GCD(a, b).
+pub fn extended_gcd_with_quotients(a: u64, b: u64) -> (i64, i64, i64, Vec<u64>) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1i128, 0i128);
+    let (mut old_y, mut y) = (0i128, 1i128);
+    let mut quotients = Vec::new();
+
+    while r != 0 {
+        let q = old_r / r;
+        quotients.push(q);
+        (old_r, r) = (r, old_r % r);
+        (old_x, x) = (x, old_x - q as i128 * x);
+        (old_y, y) = (y, old_y - q as i128 * y);
+    }
+
+    (old_x as i64, old_y as i64, old_r as i64, quotients)
+}
+
+//dev:      This function counts how many rows the inverse loop goes through for a under Mod m,
+//          i.e. how many times it divides before B becomes 0.
+//returns:  Returns the number of iterations. It is never more than max_iterations_for_modulus(m) for a < m.
+pub fn inverse_iterations(a: u64, m: u64) -> u32 {
+    let (mut big, mut small) = (m, a);
+    let mut iterations = 0;
+
+    while small > 0 {
+        (big, small) = (small, big % small);
+        iterations += 1;
+    }
+
+    iterations
+}
+
+//notice:   The average number of division steps of the Euclidean algorithm grows like (12 ln 2 / π^2) ln m ≈ 0.843 ln m,
+//          which is well below the worst case given by Lamé's theorem.
+//dev:      This function measures that average directly by counting the iterations for every unit of m.
+//returns:  Returns the mean number of iterations over units_mod(m), or 0 if m has no units (m = 0).
+pub fn average_iterations(m: u64) -> f64 {
+    let units = units_mod(m);
+
+    if units.is_empty() {
+        return 0.0;
+    }
+
+    let total: u64 = units.iter().map(|&a| inverse_iterations(a, m) as u64).sum();
+    total as f64 / units.len() as f64
+}
+
+//notice:   Under Mod m a fraction num / den means num times the inverse of den, so it has a value exactly
+//          when den has an inverse. Example: 1/3 Mod 7 is 5, because 3 * 5 = 15 ≅ 1.
+//dev:      This function turns a fraction with a possibly negative numerator into a residue, which is the basic step
+//          for doing rational arithmetic under a modulus.
+//returns:  Returns Some(residue) in [0, m), or None if den has no inverse under Mod m.
+pub fn reduce_fraction_mod(num: i64, den: u64, m: u64) -> Option<u64> {
+    let inverse_den = inverse(den, m)?;
+
+    //rem_euclid keeps the remainder of a negative numerator in [0, m)
+    let num = (num as i128).rem_euclid(m as i128) as u64;
+    Some(mod_mul(num, inverse_den, m))
+}
+
+//dev:      The range an answer Mod m is reported in. Every range holds exactly one number from each residue class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CanonicalRange {
+    //[0, m), the usual remainder
+    LeastNonNegative,
+    //(-m/2, m/2], the representative closest to 0
+    Balanced,
+    //[1, m], which uses m instead of 0
+    LeastPositive,
+}
+
+//dev:      This function calculates the inverse of a under Mod m and reports it in the chosen range.
+//          Example: the inverse of 3 Mod 5 is 2 in every range, while the inverse of 2 Mod 5 is 3, or -2 when balanced.
+//returns:  Returns the inverse in the chosen range, or None if it doesn't exist or doesn't fit in an i64.
+pub fn inverse_in_range(a: u64, m: u64, range: CanonicalRange) -> Option<i64> {
+    let x = inverse(a, m)? as i128;
+    let m = m as i128;
+
+    let representative = match range {
+        CanonicalRange::LeastNonNegative => x,
+        CanonicalRange::Balanced if x > m / 2 => x - m,
+        CanonicalRange::Balanced => x,
+        CanonicalRange::LeastPositive if x == 0 => m,
+        CanonicalRange::LeastPositive => x,
+    };
+
+    i64::try_from(representative).ok()
+}
+
+//dev:      This function calculates the inverse of two numbers given as text. Both accept the same expressions
+//          as parse_modulus_expr (e.g. "10" and "1e9+7"). A parse failure comes back as InverseError::Parse,
+//          with the ParseError reachable through source().
+//returns:  Returns the inverse of a under Mod m, or an InverseError describing what went wrong.
+pub fn inverse_str(a: &str, m: &str) -> Result<u64, InverseError> {
+    let parse = |input: &str| {
+        parse::parse_modulus_expr(input).map_err(|source| InverseError::Parse { input: input.to_string(), source })
+    };
+
+    try_inverse(parse(a)?, parse(m)?)
+}
+
+//notice:   This is the definition of the inverse turned straight into code: try every x and keep the one with ax ≅ 1.
+//dev:      This function is a slow, obviously correct reference for checking the Extended Euclidean Algorithm.
+//          It takes up to m multiplications, so it is only practical for small moduli.
+//returns:  Returns the smallest x in [0, m) with (a * x) mod m = 1 mod m, or None if there isn't one.
+pub fn find_inverse_by_search(a: u64, m: u64) -> Option<u64> {
+    if m == 0 {
+        return None;
+    }
+
+    //1 mod m is 1 for every m except 1, where everything is 0
+    (0..m).find(|&x| mod_mul(a, x, m) == 1 % m)
+}
+
+//dev:      Everything the Extended Euclidean Algorithm works out for a and m, gathered in one place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InverseReport {
+    //x normalized into [0, m), i.e. the modular multiplicative inverse of a
+    pub inverse: u64,
+    //the coefficients of Bézout's identity a * bezout_x + m * bezout_y = gcd, exactly as the loop left them
+    pub bezout_x: i64,
+    pub bezout_y: i64,
+    //GCD(a, m), which is always 1 when there is an inverse
+    pub gcd: u64,
+}
+
+//dev:      This function runs the Extended Euclidean Algorithm on a and m and returns the whole result instead of
+//          only the inverse, which is handy for checking the algorithm by hand against the table in the documentation.
+//          Example: for a = 3 and m = 5 it gives bezout_x = 2 and bezout_y = -1, since 3 * 2 + 5 * (-1) = 1.
+//returns:  Returns Some(report) if a has an inverse under Mod m, or None if it doesn't, if m is 0,
+//          or if a Bézout coefficient doesn't fit in an i64.
+pub fn inverse_full(a: u64, m: u64) -> Option<InverseReport> {
+    if m == 0 {
+        return None;
+    }
+
+    let (mut old_r, mut r) = (a, m);
+    let (mut old_x, mut x) = (1i128, 0i128);
+    let (mut old_y, mut y) = (0i128, 1i128);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r % r);
+        (old_x, x) = (x, old_x - q as i128 * x);
+        (old_y, y) = (y, old_y - q as i128 * y);
+    }
+
+    if old_r != 1 {
+        return None;
+    }
+
+    Some(InverseReport {
+        inverse: old_x.rem_euclid(m as i128) as u64,
+        bezout_x: i64::try_from(old_x).ok()?,
+        bezout_y: i64::try_from(old_y).ok()?,
+        gcd: old_r,
+    })
+}
+
+//dev:          This function calculates the inverse for realtime and embedded code, where even a rare panic or
+//              allocation is not allowed. It keeps a fixed handful of integers on the stack, doesn't recurse,
+//              doesn't allocate or format anything, and only uses core arithmetic, so it is async-signal-safe
+//              and would work unchanged in a no_std build.
+//              Every way the other versions can panic is ruled out here: m = 0 is checked before the first division,
+//              the loop divides by B only while B > 0, and the coefficients never get larger than m in absolute value,
+//              so the wrapping operations on them below never actually wrap.
+//returns:      Returns Some(inverse) if a has an inverse under Mod m, None otherwise (including when m is 0).
+#[allow(non_snake_case)]
+pub fn inverse_rt(a: u64, m: u64) -> Option<u64> {
+    if m == 0 {
+        return None;
+    }
+
+    let mut x: i128 = 0;
+    let mut y: i128 = 1;
+    let mut A = m;
+    let mut B = a % m;
+
+    while B > 0 {
+        let q = A / B;
+        let t = x.wrapping_sub(y.wrapping_mul(q as i128));
+
+        (A, B) = (B, A % B);
+        (x, y) = (y, t);
+    }
+
+    if A != 1 {
+        return None;
+    }
+
+    //for m = 1 the loop doesn't run and x = 0, which is the right answer
+    if x < 0 {
+        x = x.wrapping_add(m as i128);
+    }
+
+    Some(x as u64)
+}
+
+//notice:   Signature schemes like ECDSA work in a cyclic group of order n and need k^-1 Mod n for a secret nonce k,
+//          e.g. s = k^-1 * (z + r * d) mod n. Exponents in such a group only matter Mod n, which is why the inverse
+//          is taken under the group order and not under the modulus of the underlying field.
+//dev:      This function is inverse(k, group_order) with the extra checks that use case needs. A nonce of 0 Mod n
+//          is rejected, and so is a group order below 2, since the trivial group has no meaningful scalars.
+//          For a prime group order (the usual case) every other k is accepted.
+//returns:  Returns Some(k^-1 mod group_order), or None if k is 0 Mod n or isn't relatively prime to the group order.
+pub fn scalar_inverse(k: u64, group_order: u64) -> Option<u64> {
+    if group_order < 2 || k.is_multiple_of(group_order) {
+        return None;
+    }
+
+    inverse(k, group_order)
+}
+
+//notice:   The convergents of a continued fraction [a0; a1, a2, ...] are the fractions p_k / q_k it gives when cut off
+//          after a_k. Their denominators follow q_k = a_k * q_(k-1) + q_(k-2), starting from q_(-2) = 1 and q_(-1) = 0.
+//          Example: 43 / 19 = [2; 3, 1, 4] has the convergents 2/1, 7/3, 9/4 and 43/19.
+//dev:      This function builds the convergent denominators from the partial quotients (e.g. the quotients from
+//          extended_gcd_with_quotients) and inverts each one under Mod m. The denominators grow exponentially,
+//          so they are only ever kept reduced under Mod m, which is all the inverse depends on.
+//returns:  Returns one entry per partial quotient: Some(inverse of q_k) or None if q_k has no inverse under Mod m.
+pub fn convergent_denominator_inverses(partial_quotients: &[u64], m: u64) -> Vec<Option<u64>> {
+    if m == 0 {
+        return vec![None; partial_quotients.len()];
+    }
+
+    let (mut before_previous, mut previous) = (1 % m, 0);
+
+    partial_quotients
+        .iter()
+        .map(|&a| {
+            let q = mod_add(mod_mul(a, previous, m), before_previous, m);
+            (before_previous, previous) = (previous, q);
+            inverse(q, m)
+        })
+        .collect()
+}
+
+//notice:   Every number has an additive inverse Mod m, the number that adds to it to give 0, but only the units
+//          have a multiplicative inverse, the number that multiplies with it to give 1. The two are easy to mix up,
+//          since they sometimes match. Example: Mod 5, 3 + 2 ≅ 0 and 3 * 2 ≅ 1, so both inverses of 3 are 2,
+//          but 4 + 1 ≅ 0 while 4 * 4 ≅ 1, so the inverses of 4 are 1 and 4.
+//dev:          This function calculates both inverses of a under Mod m, side by side.
+//Assumption:   Assumes m > 0
+//returns:      Returns (additive inverse, Some(multiplicative inverse)), with None when there is no multiplicative inverse.
+pub fn both_inverses(a: u64, m: u64) -> (u64, Option<u64>) {
+    ((m - a % m) % m, inverse(a, m))
+}
+
+//notice:   Inversion maps the units of m onto themselves, and doing it twice gives back where you started.
+//          So the whole map is a set of pairs (a, b) with b = a^-1, where (b, a) is always in the set as well,
+//          and a unit that is its own inverse pairs with itself. Example: Mod 10 the pairs are
+//          (1, 1), (3, 7), (7, 3) and (9, 9).
+//dev:      This function lists the inversion map of m in full, using pair_unit_inverses on units_mod(m),
+//          so only about half the inverses are actually calculated.
+//returns:  Returns φ(m) pairs (a, inverse of a), with a running through the units in increasing order.
+pub fn inverse_pairs(m: u64) -> Vec<(u64, u64)> {
+    batch::pair_unit_inverses(&units_mod(m), m)
+}
+
+//dev:      This function calculates the inverse, and when there isn't one, hands GCD(a, m) to fallback and returns
+//          whatever it gives back, much like Option::unwrap_or_else. The gcd is the factor standing in the way,
+//          which is usually what the caller needs to decide on a substitute. fallback is only called on failure.
+//          Example: inverse_or_else(4, 10, |g| g) returns 2, because 4 and 10 share the factor 2.
+//returns:  Returns the inverse of a under Mod m, or fallback(GCD(a, m)) if there is none (for m = 0 that gcd is a).
+pub fn inverse_or_else(a: u64, m: u64, fallback: impl FnOnce(u64) -> u64) -> u64 {
+    match inverse(a, m) {
+        Some(x) => x,
+        None => fallback(gcd(a, m)),
+    }
+}
+
+//dev:      This function calculates the inverse of (a + offset) mod m. The sum goes through mod_add, so it can't
+//          overflow even when a + offset is bigger than a u64.
+//returns:  Returns the inverse of a + offset under Mod m, or None if it doesn't exist or m is 0.
+pub fn inverse_offset(a: u64, offset: u64, m: u64) -> Option<u64> {
+    if m == 0 {
+        return None;
+    }
+
+    inverse(mod_add(a, offset, m), m)
+}
+
+//dev:      This function calculates the inverse of a under Mod m and reports it as the one representative in
+//          [min_rep, min_rep + m), which generalises the fixed choices of CanonicalRange to any lower bound.
+//          Example: the inverse of 3 Mod 5 is 2, which is 2 for min_rep = 0 or 1, 7 for min_rep = 5 and 12 for min_rep = 10.
+//returns:  Returns the inverse in [min_rep, min_rep + m), or None if it doesn't exist or doesn't fit in a u64.
+pub fn inverse_representative(a: u64, m: u64, min_rep: u64) -> Option<u64> {
+    let x = inverse(a, m)?;
+
+    //the distance from min_rep up to the next number congruent to x
+    min_rep.checked_add(mod_sub(x, min_rep, m))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tiny_iteration_limit_is_reported() {
+        //34 and 55 are consecutive Fibonacci numbers, the pair that needs the most rows for its size
+        assert_eq!(try_inverse_with_limit(34, 55, 1), Err(InverseError::IterationLimitExceeded { limit: 1 }));
+        assert_eq!(try_inverse_with_limit(34, 55, 0), Err(InverseError::IterationLimitExceeded { limit: 0 }));
+        assert_eq!(try_inverse_with_limit(34, 55, max_iterations_for_modulus(55) + 2), Ok(34));
+        //the fast paths for Mod 1 and Mod 2 take no rows, so no limit can stop them
+        assert_eq!(try_inverse_with_limit(5, 1, 0), Ok(0));
+        assert_eq!(try_inverse_with_limit(5, 2, 0), Ok(1));
+    }
+
+    #[test]
+    fn default_limit_never_trips() {
+        for m in 1..200 {
+            for a in 0..m {
+                assert!(!matches!(try_inverse(a, m), Err(InverseError::IterationLimitExceeded { .. })));
+            }
+        }
+        //F(92) and F(93), the worst case that fits in a u64
+        let x = try_inverse(7540113804746346429, 12200160415121876738).unwrap();
+        assert_eq!(mod_mul(x, 7540113804746346429, 12200160415121876738), 1);
+    }
+
+    #[test]
+    fn mod_two_has_one_unit() {
+        assert_eq!(inverse(1, 2), Some(1));
+        assert_eq!(inverse(0, 2), None);
+        assert_eq!(inverse(u64::MAX, 2), Some(1));
+        assert_eq!(try_inverse(4, 2), Err(InverseError::NotRelativelyPrime { a: 4, m: 2, gcd: 2 }));
+        assert_eq!(modular_multiplicative_inverse(3, 2), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "aren't relatively prime")]
+    fn mod_two_panics_on_even_input() {
+        modular_multiplicative_inverse(0, 2);
+    }
+
+    #[test]
+    fn gcd_invariant_holds_on_every_row() {
+        for &(a, b) in &[(3, 7), (240, 46), (46, 240), (0, 5), (5, 0), (34, 55), (u64::MAX, u64::MAX - 1)] {
+            assert!(check_gcd_invariant(a, b), "a = {}, b = {}", a, b);
+        }
+    }
+
+    #[test]
+    fn library_inverse_matches_the_table_example() {
+        assert_eq!(modular_multiplicative_inverse(3, 7), 5);
+        assert_eq!(modular_multiplicative_inverse(10, 17), 12);
+        assert_eq!(modular_multiplicative_inverse(5, 1), 0);
+        assert!(is_relatively_prime(8, 15));
+        assert_eq!(gcd(240, 46), 2);
+    }
+
+    #[test]
+    fn explain_no_inverse_names_the_gcd() {
+        assert_eq!(
+            explain_no_inverse(6, 9).as_deref(),
+            Some("gcd(6, 9) = 3, so 6 has no inverse mod 9; any inverse would require 3 | 1, which is impossible.")
+        );
+        assert_eq!(explain_no_inverse(4, 9), None);
+        assert!(explain_no_inverse(0, 10).unwrap().contains("gcd(0, 10) = 10"));
+    }
+
+    #[test]
+    fn units_and_non_units_split_the_residues() {
+        assert_eq!(non_units_mod(12), vec![0, 2, 3, 4, 6, 8, 9, 10]);
+        assert_eq!(units_mod(12), vec![1, 5, 7, 11]);
+        for n in 0..50 {
+            assert_eq!(units_mod(n).len() + non_units_mod(n).len(), n as usize);
+        }
+        assert_eq!(units_mod(1), vec![0]);
+        assert!(non_units_mod(1).is_empty());
+    }
+
+    #[test]
+    fn inverse_of_product_matches_the_direct_product() {
+        for m in 2..40 {
+            for a in 0..m {
+                for b in 0..m {
+                    assert_eq!(inverse_of_product(a, b, m), inverse((a * b) % m, m), "a = {}, b = {}, m = {}", a, b, m);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn inverse_of_product_survives_huge_operands() {
+        let m = 18446744073709551557;
+        let x = inverse_of_product(u64::MAX - 1, u64::MAX - 2, m).unwrap();
+        assert_eq!(mod_mul(mod_mul(u64::MAX - 1, u64::MAX - 2, m), x, m), 1);
+        assert_eq!(mod_mul(u64::MAX, u64::MAX, u64::MAX - 1), 1);
+    }
+
+    #[test]
+    fn inverse_of_ten_steps_back_through_the_decimal_remainders() {
+        assert_eq!(inverse_of_ten(7), Some(5));
+        assert_eq!(inverse_of_ten(7), inverse(10, 7));
+        //the remainders of 1/7 are 1, 3, 2, 6, 4, 5; multiplying by 5 walks them backwards
+        assert_eq!(mod_mul(3, 5, 7), 1);
+        assert_eq!(mod_mul(2, 5, 7), 3);
+        assert_eq!(inverse_of_ten(12), None);
+        assert_eq!(inverse_of_ten(1), Some(0));
+    }
+
+    #[test]
+    fn precomputed_gcd_is_checked() {
+        assert_eq!(inverse_with_precomputed_gcd(3, 7, 1), Ok(5));
+        assert_eq!(inverse_with_precomputed_gcd(3, 7, 3), Err(InverseError::GcdMismatch { claimed: 3, actual: 1 }));
+        assert_eq!(inverse_with_precomputed_gcd(4, 10, 2), Err(InverseError::NotRelativelyPrime { a: 4, m: 10, gcd: 2 }));
+        assert_eq!(inverse_with_precomputed_gcd(4, 0, 4), Err(InverseError::ZeroModulus));
+    }
+
+    #[test]
+    fn precomputed_gcd_handles_moduli_past_i64() {
+        //this overflowed the i64 loop it used to finish with
+        assert_eq!(inverse_with_precomputed_gcd(2, 18446744073709551557, 1), try_inverse(2, 18446744073709551557));
+        assert_eq!(inverse_with_precomputed_gcd(2, 18446744073709551557, 1), Ok(9223372036854775779));
+    }
+
+    #[test]
+    fn inverse_delta_matches_a_fresh_inverse() {
+        let m = 101;
+        for a in 1..m {
+            let prev = inverse(a, m).unwrap();
+            for d in [0, 1, 5, 100, u64::MAX] {
+                assert_eq!(inverse_delta(a, d, m, prev), inverse(((a as u128 + d as u128) % m as u128) as u64, m));
+            }
+        }
+        assert_eq!(inverse_delta(3, 1, 10, 7), None);
+        assert_eq!(inverse_delta(u64::MAX, u64::MAX, 7, inverse(u64::MAX, 7).unwrap()), inverse(2, 7));
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "is not the inverse")]
+    fn inverse_delta_checks_its_contract_in_debug_builds() {
+        inverse_delta(3, 1, 7, 4);
+    }
+
+    #[test]
+    fn extended_gcd_satisfies_bezout() {
+        assert_eq!(extended_gcd(240, 46), (-9, 47, 2));
+        for &(a, b) in &[(3, 5), (-3, 5), (3, -5), (-3, -5), (0, 7), (7, 0), (0, 0), (i64::MAX, i64::MAX - 1)] {
+            let (x, y, g) = extended_gcd(a, b);
+            assert!(g >= 0);
+            assert_eq!(a as i128 * x as i128 + b as i128 * y as i128, g as i128, "a = {}, b = {}", a, b);
+            assert_eq!(g as u64, gcd(a.unsigned_abs(), b.unsigned_abs()));
+        }
+    }
+
+    #[test]
+    fn smallest_invertible_modulus_skips_shared_factors() {
+        assert_eq!(smallest_invertible_modulus(6, 2..=12), Some(5));
+        assert_eq!(smallest_invertible_modulus(6, 2..=4), None);
+        assert_eq!(smallest_invertible_modulus(7, 0..=10), Some(2));
+        assert_eq!(smallest_invertible_modulus(0, 0..=100), None);
+    }
+
+    #[test]
+    fn quotients_are_the_continued_fraction() {
+        //43 / 19 = 2 + 1 / (3 + 1 / (1 + 1 / 4))
+        let (x, y, g, quotients) = extended_gcd_with_quotients(43, 19);
+        assert_eq!(quotients, vec![2, 3, 1, 4]);
+        assert_eq!((43 * x + 19 * y, g), (1, 1));
+
+        //rebuilding the fraction from the quotients, back to front
+        let (num, den) = quotients.iter().rev().skip(1).fold((4u64, 1u64), |(num, den), &q| (q * num + den, num));
+        assert_eq!((num, den), (43, 19));
+
+        assert_eq!(extended_gcd_with_quotients(19, 43).3, vec![0, 2, 3, 1, 4]);
+        assert_eq!(extended_gcd_with_quotients(5, 0), (1, 0, 5, Vec::new()));
+    }
+
+    #[test]
+    fn average_iterations_stays_under_the_lame_bound() {
+        //the units of 5 take 1, 2, 3 and 2 rows
+        assert_eq!(inverse_iterations(3, 5), 3);
+        assert_eq!(average_iterations(5), 2.0);
+        assert_eq!(max_iterations_for_modulus(5), 3);
+        for m in 2..300 {
+            assert!(average_iterations(m) <= max_iterations_for_modulus(m) as f64);
+            assert!(units_mod(m).iter().all(|&a| inverse_iterations(a, m) <= max_iterations_for_modulus(m)));
+        }
+        assert_eq!(average_iterations(0), 0.0);
+        assert_eq!(inverse_iterations(34, 55), max_iterations_for_modulus(55));
+    }
+
+    #[test]
+    fn reduce_fraction_mod_handles_negative_numerators() {
+        assert_eq!(reduce_fraction_mod(1, 3, 7), Some(5));
+        //-2/5 Mod 11: the inverse of 5 is 9 and -2 is 9, so 9 * 9 = 81 ≅ 4
+        assert_eq!(reduce_fraction_mod(-2, 5, 11), Some(4));
+        assert_eq!(mod_mul(4, 5, 11), 9);
+        assert_eq!(reduce_fraction_mod(1, 4, 10), None);
+        assert_eq!(reduce_fraction_mod(i64::MIN, 1, 7), Some((i64::MIN as i128).rem_euclid(7) as u64));
+    }
+
+    #[test]
+    fn inverse_in_each_range() {
+        //the inverse of 2 Mod 5 is 3
+        assert_eq!(inverse_in_range(2, 5, CanonicalRange::LeastNonNegative), Some(3));
+        assert_eq!(inverse_in_range(2, 5, CanonicalRange::Balanced), Some(-2));
+        assert_eq!(inverse_in_range(2, 5, CanonicalRange::LeastPositive), Some(3));
+        //Mod 1 the inverse is 0, which LeastPositive reports as 1
+        assert_eq!(inverse_in_range(5, 1, CanonicalRange::LeastNonNegative), Some(0));
+        assert_eq!(inverse_in_range(5, 1, CanonicalRange::LeastPositive), Some(1));
+        //m / 2 itself stays positive in the balanced range: the inverse of 3 Mod 4 is 3, balanced -1
+        assert_eq!(inverse_in_range(3, 4, CanonicalRange::Balanced), Some(-1));
+        assert_eq!(inverse_in_range(4, 10, CanonicalRange::Balanced), None);
+        //a representative past i64::MAX doesn't fit
+        assert_eq!(inverse_in_range(u64::MAX - 1, u64::MAX, CanonicalRange::LeastNonNegative), None);
+        assert_eq!(inverse_in_range(u64::MAX - 1, u64::MAX, CanonicalRange::Balanced), Some(-1));
+    }
+
+    #[test]
+    fn brute_force_search_agrees_with_the_inverse() {
+        for m in 1..80 {
+            for a in 0..m {
+                let found = find_inverse_by_search(a, m);
+                assert_eq!(found, inverse(a, m), "a = {}, m = {}", a, m);
+                if is_relatively_prime(a, m) {
+                    assert_eq!(found, Some(modular_multiplicative_inverse(a, m)));
+                }
+            }
+        }
+        assert_eq!(find_inverse_by_search(3, 0), None);
+    }
+
+    #[test]
+    fn inverse_full_satisfies_bezout() {
+        assert_eq!(inverse_full(3, 5), Some(InverseReport { inverse: 2, bezout_x: 2, bezout_y: -1, gcd: 1 }));
+        for &(a, m) in &[(3u64, 7u64), (10, 17), (34, 55), (5, 1), (u64::MAX - 1, u64::MAX)] {
+            let report = inverse_full(a, m).unwrap();
+            assert_eq!(a as i128 * report.bezout_x as i128 + m as i128 * report.bezout_y as i128, report.gcd as i128);
+            assert_eq!(report.inverse, (report.bezout_x as i128).rem_euclid(m as i128) as u64);
+            assert_eq!(Some(report.inverse), inverse(a, m));
+        }
+        assert_eq!(inverse_full(4, 10), None);
+        assert_eq!(inverse_full(4, 0), None);
+    }
+
+    #[test]
+    fn inverse_rt_matches_try_inverse() {
+        for m in 0..120 {
+            for a in (0..130).chain([u64::MAX]) {
+                assert_eq!(inverse_rt(a, m), try_inverse(a, m).ok(), "a = {}, m = {}", a, m);
+            }
+        }
+        for &(a, m) in &[(7540113804746346429, 12200160415121876738), (u64::MAX - 1, u64::MAX), (2, 18446744073709551557)] {
+            assert_eq!(inverse_rt(a, m), try_inverse(a, m).ok());
+        }
+    }
+
+    #[test]
+    fn scalar_inverse_under_a_prime_group_order() {
+        //2^61 - 1 is prime
+        let n = (1u64 << 61) - 1;
+        for k in [1, 2, 12345, n - 1, n + 5] {
+            let k_inverse = scalar_inverse(k, n).unwrap();
+            assert_eq!(mod_mul(k, k_inverse, n), 1);
+        }
+        assert_eq!(scalar_inverse(0, n), None);
+        assert_eq!(scalar_inverse(n, n), None);
+        assert_eq!(scalar_inverse(6, 12), None);
+        assert_eq!(scalar_inverse(1, 1), None);
+    }
+
+    #[test]
+    fn convergent_denominators_of_43_over_19() {
+        //the denominators are 1, 3, 4 and 19
+        assert_eq!(convergent_denominator_inverses(&[2, 3, 1, 4], 10), vec![Some(1), Some(7), None, Some(9)]);
+        assert_eq!(convergent_denominator_inverses(&[2, 3, 1, 4], 101), vec![
+            inverse(1, 101),
+            inverse(3, 101),
+            inverse(4, 101),
+            inverse(19, 101)
+        ]);
+        assert_eq!(convergent_denominator_inverses(&[2, 3], 0), vec![None, None]);
+        assert!(convergent_denominator_inverses(&[], 10).is_empty());
+    }
+
+    #[test]
+    fn both_inverses_side_by_side() {
+        //the two happen to match here
+        assert_eq!(both_inverses(3, 5), (2, Some(2)));
+        assert_eq!(both_inverses(2, 5), (3, Some(3)));
+        //and here they don't
+        assert_eq!(both_inverses(4, 5), (1, Some(4)));
+        assert_eq!(both_inverses(4, 10), (6, None));
+        assert_eq!(both_inverses(0, 7), (0, None));
+        assert_eq!(both_inverses(12, 5), (3, Some(3)));
+    }
+
+    #[test]
+    fn inverse_pairs_describe_the_whole_inversion_map() {
+        assert_eq!(inverse_pairs(10), vec![(1, 1), (3, 7), (7, 3), (9, 9)]);
+
+        for m in [2, 7, 12, 36, 97, 100, 210] {
+            let pairs = inverse_pairs(m);
+            assert_eq!(pairs.len() as u64, number_theory::euler_totient(m));
+            for &(a, b) in &pairs {
+                assert_eq!(a * b % m, 1);
+                assert!(pairs.contains(&(b, a)));
+            }
+        }
+    }
+
+    #[test]
+    fn inverse_or_else_only_calls_the_fallback_on_failure() {
+        let mut seen = None;
+        assert_eq!(
+            inverse_or_else(4, 10, |g| {
+                seen = Some(g);
+                99
+            }),
+            99
+        );
+        assert_eq!(seen, Some(2));
+
+        assert_eq!(inverse_or_else(3, 7, |_| panic!("3 has an inverse Mod 7")), 5);
+        assert_eq!(inverse_or_else(4, 10, |g| g), 2);
+        assert_eq!(inverse_or_else(6, 0, |g| g), 6);
+    }
+
+    #[test]
+    fn inverse_offset_reduces_the_sum_without_overflowing() {
+        assert_eq!(inverse_offset(1, 2, 7), inverse(3, 7));
+        assert_eq!(inverse_offset(4, 6, 10), None);
+
+        //u64::MAX ≅ 2 (mod 13), so u64::MAX + 11 ≅ 0 even though the sum doesn't fit in a u64
+        assert_eq!(inverse_offset(u64::MAX, 11, 13), None);
+        assert_eq!(inverse_offset(u64::MAX, 12, 13), Some(1));
+
+        let m = 1_000_000_007;
+        let sum = (u64::MAX as u128 + 10) % m as u128;
+        assert_eq!(inverse_offset(u64::MAX, 10, m), inverse(sum as u64, m));
+        assert_eq!(inverse_offset(u64::MAX, u64::MAX, m), inverse(((u64::MAX as u128 * 2) % m as u128) as u64, m));
+        assert_eq!(inverse_offset(3, 2, 0), None);
+    }
+
+    #[test]
+    fn inverse_representative_shifts_into_the_requested_range() {
+        assert_eq!(inverse_representative(3, 5, 0), Some(2));
+        assert_eq!(inverse_representative(3, 5, 1), Some(2));
+        assert_eq!(inverse_representative(3, 5, 3), Some(7));
+        assert_eq!(inverse_representative(3, 5, 5), Some(7));
+        assert_eq!(inverse_representative(3, 5, 10), Some(12));
+
+        //for m > 1 the inverse is never 0, so [1, m] gives the same answer as [0, m)
+        for a in 1..10 {
+            assert_eq!(inverse_representative(a, 10, 1), inverse(a, 10));
+        }
+        //Mod 1 the inverse is 0, and [1, 2) holds only 1
+        assert_eq!(inverse_representative(5, 1, 1), Some(1));
+
+        assert_eq!(inverse_representative(2, 10, 1), None);
+        assert_eq!(inverse_representative(3, 5, u64::MAX - 2), None);
+        assert_eq!(inverse_representative(3, 5, u64::MAX - 4), Some(u64::MAX - 3));
+    }
+
+    #[test]
+    fn reduced_and_guarded_loops_match_the_inverse() {
+        for m in [1, 2, 10, 97, 360, 1_000_000_007, (1 << 40) - 1, i64::MAX as u64, u64::MAX - 1, u64::MAX] {
+            for a in [0, 1, 2, 3, 7, 12_345, m - 1, m.wrapping_add(2), u64::MAX] {
+                let expected = inverse(a, m);
+                assert_eq!(inverse_reduced_coeffs(a, m), expected, "a = {}, m = {}", a, m);
+                assert_eq!(inverse_u128_guarded(a, m), expected, "a = {}, m = {}", a, m);
+            }
+        }
+        assert_eq!(inverse_reduced_coeffs(3, 0), None);
+        assert_eq!(inverse_u128_guarded(3, 0), None);
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{inverse, mod_mul, mod_sub};
+
+//notice:   Gaussian elimination turns a matrix into an upper triangular one using two kinds of row operations:
+//          swapping two rows, which flips the sign of the determinant, and subtracting a multiple of one row from
+//          another, which leaves it unchanged. The determinant is then the product of the diagonal, the pivots.
+//          Over Mod p the multiple is (entry / pivot), i.e. entry times the inverse of the pivot.
+//dev:          This function calculates the determinant of a square matrix under Mod p by Gaussian elimination.
+//Assumption:   Assumes p is prime, so that every nonzero pivot has an inverse
+//returns:      Returns Some(determinant mod p), which is 0 for a singular matrix, or None if the matrix isn't square,
+//              p is 0, or a pivot has no inverse because p isn't prime.
+pub fn modular_determinant(matrix: &[Vec<u64>], p: u64) -> Option<u64> {
+    let n = matrix.len();
+    if p == 0 || matrix.iter().any(|row| row.len() != n) {
+        return None;
+    }
+
+    let mut rows: Vec<Vec<u64>> = matrix.iter().map(|row| row.iter().map(|&v| v % p).collect()).collect();
+    let mut determinant = 1 % p;
+
+    for column in 0..n {
+        //a column with no nonzero entry left means the matrix is singular
+        let Some(pivot_row) = (column..n).find(|&r| rows[r][column] != 0) else {
+            return Some(0);
+        };
+
+        if pivot_row != column {
+            rows.swap(pivot_row, column);
+            determinant = mod_sub(0, determinant, p);
+        }
+
+        let pivot = rows[column][column];
+        let pivot_inverse = inverse(pivot, p)?;
+        determinant = mod_mul(determinant, pivot, p);
+
+        let (upper, lower) = rows.split_at_mut(column + 1);
+        let pivot_values = &upper[column];
+        for row in lower {
+            let factor = mod_mul(row[column], pivot_inverse, p);
+            if factor == 0 {
+                continue;
+            }
+            for (entry, &pivot_value) in row.iter_mut().zip(pivot_values).skip(column) {
+                *entry = mod_sub(*entry, mod_mul(factor, pivot_value, p), p);
+            }
+        }
+    }
+
+    Some(determinant)
+}
+
+//notice:   Gauss-Jordan elimination goes one step further than Gaussian elimination: every pivot row is scaled so the
+//          pivot becomes 1, and its column is cleared above the pivot as well as below. What is left of the matrix is
+//          the identity, so the right-hand side has turned into the solution.
+//dev:          This function solves the system matrix * x = rhs under Mod p by Gauss-Jordan elimination,
+//              using the inverse of each pivot to scale its row.
+//Assumption:   Assumes p is prime, so that every nonzero pivot has an inverse
+//returns:      Returns Some(x) with every equation satisfied Mod p, or None if the matrix isn't square, rhs has the wrong
+//              length, p is 0, or the matrix is singular Mod p (so there is no single solution).
+pub fn solve_linear_system_mod(matrix: &[Vec<u64>], rhs: &[u64], p: u64) -> Option<Vec<u64>> {
+    let n = matrix.len();
+    if p == 0 || rhs.len() != n || matrix.iter().any(|row| row.len() != n) {
+        return None;
+    }
+
+    //each row carries its right-hand side as an extra last column
+    let mut rows: Vec<Vec<u64>> = matrix
+        .iter()
+        .zip(rhs)
+        .map(|(row, &b)| row.iter().chain(Some(&b)).map(|&v| v % p).collect())
+        .collect();
+
+    for column in 0..n {
+        let pivot_row = (column..n).find(|&r| rows[r][column] != 0)?;
+        rows.swap(pivot_row, column);
+
+        let pivot_inverse = inverse(rows[column][column], p)?;
+        for entry in rows[column].iter_mut() {
+            *entry = mod_mul(*entry, pivot_inverse, p);
+        }
+
+        let pivot_values = rows[column].clone();
+        for (r, row) in rows.iter_mut().enumerate() {
+            let factor = row[column];
+            if r == column || factor == 0 {
+                continue;
+            }
+            for (entry, &pivot_value) in row.iter_mut().zip(&pivot_values).skip(column) {
+                *entry = mod_sub(*entry, mod_mul(factor, pivot_value, p), p);
+            }
+        }
+    }
+
+    Some(rows.into_iter().map(|row| row[n]).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn determinant_of_a_small_matrix() {
+        //2(24 - 2) - 1(0 - 5) + 3(0 - 20) = -11 ≅ 2 (mod 13)
+        let matrix = vec![vec![2, 1, 3], vec![0, 4, 1], vec![5, 2, 6]];
+        assert_eq!(modular_determinant(&matrix, 13), Some(2));
+        assert_eq!(modular_determinant(&matrix, 1_000_000_007), Some(1_000_000_007 - 11));
+    }
+
+    #[test]
+    fn determinant_row_swaps_and_singular_matrices() {
+        assert_eq!(modular_determinant(&[vec![0, 1], vec![1, 0]], 7), Some(6));
+        assert_eq!(modular_determinant(&[vec![1, 2], vec![2, 4]], 7), Some(0));
+        //13 - 6 = 7, so this one is only singular Mod 7
+        assert_eq!(modular_determinant(&[vec![1, 2], vec![3, 13]], 7), Some(0));
+        assert_eq!(modular_determinant(&[vec![1, 2], vec![3, 13]], 11), Some(7));
+        assert_eq!(modular_determinant(&[], 7), Some(1));
+        assert_eq!(modular_determinant(&[vec![1, 2]], 7), None);
+        assert_eq!(modular_determinant(&[vec![1]], 0), None);
+    }
+
+    #[test]
+    fn solution_satisfies_every_equation() {
+        let p = 101;
+        let matrix = vec![vec![0, 3, 7], vec![5, 1, 2], vec![9, 4, 100]];
+        let rhs = [10, 20, 30];
+        let x = solve_linear_system_mod(&matrix, &rhs, p).unwrap();
+
+        for (row, &b) in matrix.iter().zip(&rhs) {
+            let left = row.iter().zip(&x).map(|(&a, &v)| a * v).sum::<u64>() % p;
+            assert_eq!(left, b);
+        }
+    }
+
+    #[test]
+    fn singular_and_malformed_systems_have_no_solution() {
+        assert_eq!(solve_linear_system_mod(&[vec![1, 2], vec![2, 4]], &[1, 2], 7), None);
+        assert_eq!(solve_linear_system_mod(&[vec![1, 2], vec![3, 13]], &[1, 1], 7), None);
+        assert_eq!(solve_linear_system_mod(&[vec![1, 2], vec![3, 13]], &[1], 11), None);
+        assert_eq!(solve_linear_system_mod(&[vec![1]], &[1], 0), None);
+        assert_eq!(solve_linear_system_mod(&[vec![2]], &[3], 7), Some(vec![5]));
+    }
+}
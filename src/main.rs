@@ -1,4 +1,9 @@
 
+use kvstore::try_inverse;
+use kvstore::parse::parse_modulus_expr;
+use std::env;
+use std::fmt;
+use std::process;
 
 ///@title This program solves for the modular multiplicative inverse of a number A under Mod B using Euclidean Algorithm
 ///@author Jelo
@@ -45,17 +50,43 @@
 ///         | - | 1 | 0 | - | 2 | -5| - |
 /// NB: We assume that the GCD of 3 and 5 is 1
 /// The algorithm makes repeated use of integer divisions until the divisor (B) becomes 0 
-
 //@notice:  The first entry point to any program written in rust
 fn main() {
 
+    //The two numbers can be passed on the command line, e.g. cargo run -- 3 5
+    //The modulus also accepts short expressions such as 1e9+7 or 2^31-1
+    //--base 16 reads both numbers in another base instead (e.g. cargo run -- ff 101 --base 16),
+    //and the answer is printed in that base too unless --out-base picks a different one
+    //env::args() includes the program name first, so it is skipped
+    let args: Vec<String> = env::args().collect();
+    let mut numbers: Vec<&str> = Vec::new();
+    let mut base: u32 = 10;
+    let mut out_base: Option<u32> = None;
+
+    let mut rest = args.iter().skip(1);
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--base" => base = parse_base_or_exit(rest.next()),
+            "--out-base" => out_base = Some(parse_base_or_exit(rest.next())),
+            number => numbers.push(number),
+        }
+    }
+    let out_base = out_base.unwrap_or(base);
+
     //declaration of variables in rust
-    //here we bind the values 3 and 5 to variables a and b respectively
+    //when no numbers are given, we bind the values 3 and 5 to variables a and b respectively
     //Rust is a strongly typed language, so whenever you declare a variable, you must specify the type.
     //In this case we specified a type of unsigned integer with a size of 64 bits.
     //To specify a type in rust, you use a colon followed by the type
-    let a = 3;
-    let b = 5;
+    let (a, b): (u64, u64) = match numbers.as_slice() {
+        [] => (3, 5),
+        [a, b] if base == 10 => (parse_or_exit(a, a.parse::<u64>()), parse_or_exit(b, parse_modulus_expr(b))),
+        [a, b] => (
+            parse_or_exit(a, u64::from_str_radix(a, base)),
+            parse_or_exit(b, u64::from_str_radix(b, base)),
+        ),
+        _ => usage_and_exit(&args[0]),
+    };
 
     //The println!() is a macro that displays the result of the modular multiplicative inverse on the screen
     //A macro is a concept unique to rust and is different from a function.
@@ -64,90 +95,60 @@ fn main() {
     //The downside is that macros are more difficult to write compared to functions.
     //The curly braces"{}" in the println macro is a placeholder that tells the compiler that a space in memory
     //should be reserved for a variable or value. 
-    println!("The modular multiplicative inverse of {} Mod {} is {}", a, b, modular_multiplicative_inverse(a, b));
+    let inverse = try_inverse(a, b).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(2);
+    });
+
+    println!(
+        "The modular multiplicative inverse of {} Mod {} is {}",
+        format_in_base(a, out_base),
+        format_in_base(b, out_base),
+        format_in_base(inverse, out_base)
+    );
 }
 
-//dev:          This function calculates the modular multiplicative inverse of a number a with respect to Mod b
-//              Rust is a statically typed language, so you must always specify the type for all the parameters in your function
-//returns:      Returns the modular multiplicative inverse
-//              When returning a value in rust, you must specify the return type. 
-fn modular_multiplicative_inverse(a: u64, b: u64) -> u64 {
-
-    //The mod multiplicative inverse of A with respect to Mod B is always zero whenever the value of B is 1
-    if b == 1 {
-        return 0; 
-    }
+fn usage_and_exit(program: &str) -> ! {
+    eprintln!("usage: {} [A B] [--base N] [--out-base N]", program);
+    process::exit(2);
+}
 
-    //Checks to see if the two given numbers are relatively prime
-    //Throws an error if they aren't
-    if !is_relatively_prime(a, b) {
-        //There are two types of errors in rust. Recoverable and unrecoverable error.
-        //An unrecoverable error is an error that halts the program whenever an error occurs.
-        //A panic macro is type of unrecoverable error, and that's what we used here.
-        //To end the program whenever the numbers given aren't coprime.
-        panic!("{} and {} aren't relatively prime", a, b);
+//dev:      Reads the value given after --base or --out-base, which must be between 2 and 36
+//          (the digits 0-9 followed by the letters a-z).
+fn parse_base_or_exit(arg: Option<&String>) -> u32 {
+    match arg.map(|arg| arg.parse::<u32>()) {
+        Some(Ok(base)) if (2..=36).contains(&base) => base,
+        Some(_) => {
+            eprintln!("the base must be a number from 2 to 36");
+            process::exit(2);
+        }
+        None => {
+            eprintln!("--base and --out-base need a value, e.g. --base 16");
+            process::exit(2);
+        }
     }
+}
 
-    //variables in rust are immutable by default.
-    //Because of the fact that the values of our variables will change during the course of these operations,
-    //we have to make them mutable by adding the "mut" keyword
-    //x, y and t are of type signed integer because of occassions where they become negative
-    let mut x: i64 = 0;
-    let mut y: i64 = 1;
-    let mut t: i64 = 0;
-
-    //A, B, q and r can't become negative throughout the lifecycle of the operation. 
-    //This is the reason why they are of type unsigned integer.
-    let mut A = b;
-    let mut B: u64 = a;
-    let mut q = 0;
-    let mut r: u64 = 0;
-
-    //A loop to calculate the multiplicative inverse as long as B(the divisor) isn't zero
-    while B > 0 {
-        q = A/B; //here we calculate the quotient q
-        r = A % B; //calculating the remainder r
-
-        //we had to cast the quotient q to a signed integer. the compiler will throw an error if an operation is carried out on different types.
-        //to cast/convert a type to another type in rust, you use the "as" keyword
-        t = x - y * q as i64; 
-
-
-        //this is where the shifting occurs.
-        //A takes the previous value of B.  B takes the previous value of r.  x takes the previous value of y.  y takes the previous value of t. 
-        A = B;
-        B = r;
-        x = y;
-        y = t;
+//returns:  Returns n written in the given base, using lowercase letters for digits above 9
+fn format_in_base(n: u64, base: u32) -> String {
+    if n == 0 {
+        return "0".to_string();
     }
 
-    //if the value of x is below zero, we add it up to 'b' to get a positive value for the multipicative inverse
-    if x < 0 {
-        x = b as i64 + x; //b has to be converted to a signed integer for the compiler not to throw an error.
+    let mut digits = Vec::new();
+    let mut rest = n;
+    while rest > 0 {
+        digits.push(char::from_digit((rest % base as u64) as u32, base).unwrap());
+        rest /= base as u64;
     }
 
-    //we expect a positive result (unsigned integer) as our return type. For this reason, x had to be converted to a u64 to be returned correctly.
-    return x as u64;
+    digits.iter().rev().collect()
 }
 
-//notice:   There exists a modular multiplicative inverse for a number A under Mod B iff both numbers are relatively prime
-//          or the GCD(Greatest Common Denominator) is 1
-//dev:      This function checks if two numbers are relatively prime.
-//returns:  Returns true if two numbers are relatively prime, false otherwise.
-fn is_relatively_prime(a: u64, b: u64) -> bool {
-    //Rust is an expression based language. An expression is a statement that returns a value.
-    //You can return an expression without using the return keyword.
-    //But note, you mustn't make use of semicolon at the end. Adding a semicolon converts the expression to a statement.
-    gcd(a, b) == 1
-} 
-
-//dev:          This function calculates the gcd of two numbers
-//Assumption:   Assumes a, b >= 0 
-//returns:      Returns the GCD of two integers   
-fn gcd(a: u64, b: u64) -> u64 {
-    if b == 0 {
-        return a; //GCD(a, 0) = a
-    } else {
-        return  gcd(b, a % b); //GCD(a, b) = GCD(b, a mod b)
-    }
-} 
\ No newline at end of file
+//dev:      Unwraps the parsed value of a command line argument, printing the error and exiting if parsing failed.
+fn parse_or_exit<E: fmt::Display>(arg: &str, parsed: Result<u64, E>) -> u64 {
+    parsed.unwrap_or_else(|e| {
+        eprintln!("invalid number '{}': {}", arg, e);
+        process::exit(2);
+    })
+}
@@ -0,0 +1,230 @@
+use crate::{inverse, mod_add, mod_mul, mod_pow, mod_sub};
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+
+//dev:      A number together with the modulus it lives under. The value is always kept in [0, modulus).
+//          Arithmetic between two ModInts only makes sense when they share a modulus, so mixing moduli panics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ModInt {
+    value: u64,
+    modulus: u64,
+}
+
+impl ModInt {
+    //dev:      Creates a ModInt, reducing value under Mod modulus.
+    //          Panics if modulus is 0, because there are no remainders to work with.
+    pub fn new(value: u64, modulus: u64) -> Self {
+        if modulus == 0 {
+            panic!("the modulus of a ModInt must be greater than 0");
+        }
+
+        ModInt { value: value % modulus, modulus }
+    }
+
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    pub fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    //returns:  Returns self^exp under the same modulus
+    pub fn pow(&self, exp: u64) -> Self {
+        ModInt { value: mod_pow(self.value, exp, self.modulus), modulus: self.modulus }
+    }
+
+    //returns:  Returns the modular multiplicative inverse, or None if the value isn't relatively prime to the modulus
+    pub fn inverse(&self) -> Option<Self> {
+        inverse(self.value, self.modulus).map(|value| ModInt { value, modulus: self.modulus })
+    }
+
+    fn check_modulus(&self, other: &Self) {
+        assert_eq!(self.modulus, other.modulus, "ModInt values have different moduli");
+    }
+}
+
+impl Add for ModInt {
+    type Output = ModInt;
+
+    fn add(self, other: ModInt) -> ModInt {
+        self.check_modulus(&other);
+        //the sum of two u64 values can overflow a u64, so it is formed in a u128
+        let sum = self.value as u128 + other.value as u128;
+        ModInt { value: (sum % self.modulus as u128) as u64, modulus: self.modulus }
+    }
+}
+
+impl Sub for ModInt {
+    type Output = ModInt;
+
+    fn sub(self, other: ModInt) -> ModInt {
+        self.check_modulus(&other);
+        let value = if self.value >= other.value {
+            self.value - other.value
+        } else {
+            self.modulus - (other.value - self.value)
+        };
+        ModInt { value, modulus: self.modulus }
+    }
+}
+
+impl Mul for ModInt {
+    type Output = ModInt;
+
+    fn mul(self, other: ModInt) -> ModInt {
+        self.check_modulus(&other);
+        ModInt { value: mod_mul(self.value, other.value, self.modulus), modulus: self.modulus }
+    }
+}
+
+impl fmt::Display for ModInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (mod {})", self.value, self.modulus)
+    }
+}
+
+//dev:      modint!(value, modulus) builds a ModInt.
+//          When both arguments are literals, a modulus of 0 is rejected while compiling instead of panicking at runtime.
+//          Any other expressions go through ModInt::new.
+#[macro_export]
+macro_rules! modint {
+    ($value:literal, $modulus:literal) => {{
+        const _: () = assert!($modulus > 0, "the modulus of a ModInt must be greater than 0");
+        $crate::modint::ModInt::new($value, $modulus)
+    }};
+    ($value:expr, $modulus:expr) => {
+        $crate::modint::ModInt::new($value, $modulus)
+    };
+}
+
+//notice:   Clock arithmetic is arithmetic Mod 12: 9 o'clock plus 5 hours is 2 o'clock, because 9 + 5 = 14 ≅ 2.
+//dev:      A number under a modulus N that is part of the type, e.g. Cyclic<12> for hours or Cyclic<60> for minutes.
+//          Unlike ModInt the modulus isn't stored, and mixing two different moduli is a compile error instead of a panic.
+//          A modulus of 0 is also rejected while compiling, as soon as a Cyclic<0> is created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cyclic<const N: u64> {
+    value: u64,
+}
+
+impl<const N: u64> Cyclic<N> {
+    //dev:      Creates a Cyclic, reducing value under Mod N.
+    pub const fn new(value: u64) -> Self {
+        const { assert!(N > 0, "the modulus of a Cyclic must be greater than 0") };
+        Cyclic { value: value % N }
+    }
+
+    pub const fn value(&self) -> u64 {
+        self.value
+    }
+
+    pub const fn modulus(&self) -> u64 {
+        N
+    }
+
+    //returns:  Returns self^exp under Mod N
+    pub fn pow(&self, exp: u64) -> Self {
+        Cyclic { value: mod_pow(self.value, exp, N) }
+    }
+
+    //returns:  Returns the modular multiplicative inverse, or None if the value isn't relatively prime to N
+    pub fn inverse(&self) -> Option<Self> {
+        inverse(self.value, N).map(|value| Cyclic { value })
+    }
+}
+
+impl<const N: u64> Add for Cyclic<N> {
+    type Output = Cyclic<N>;
+
+    fn add(self, other: Cyclic<N>) -> Cyclic<N> {
+        Cyclic { value: mod_add(self.value, other.value, N) }
+    }
+}
+
+impl<const N: u64> Sub for Cyclic<N> {
+    type Output = Cyclic<N>;
+
+    fn sub(self, other: Cyclic<N>) -> Cyclic<N> {
+        Cyclic { value: mod_sub(self.value, other.value, N) }
+    }
+}
+
+impl<const N: u64> Mul for Cyclic<N> {
+    type Output = Cyclic<N>;
+
+    fn mul(self, other: Cyclic<N>) -> Cyclic<N> {
+        Cyclic { value: mod_mul(self.value, other.value, N) }
+    }
+}
+
+impl<const N: u64> fmt::Display for Cyclic<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (mod {})", self.value, N)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modint_macro_with_literals_and_variables() {
+        let a = modint!(3, 7);
+        assert_eq!(a, ModInt::new(3, 7));
+
+        let (value, modulus) = (10u64, 7u64);
+        let b = modint!(value, modulus);
+        assert_eq!(b.value(), 3);
+        assert_eq!(b.modulus(), 7);
+        assert_eq!(modint!(value * 2, modulus + 4), ModInt::new(9, 11));
+    }
+
+    #[test]
+    fn modint_arithmetic() {
+        let a = modint!(5, 7);
+        let b = modint!(4, 7);
+        assert_eq!((a + b).value(), 2);
+        assert_eq!((b - a).value(), 6);
+        assert_eq!((a * b).value(), 6);
+        assert_eq!(a.pow(6).value(), 1);
+        assert_eq!(a.inverse(), Some(modint!(3, 7)));
+        assert_eq!(modint!(4, 10).inverse(), None);
+        assert_eq!(a.to_string(), "5 (mod 7)");
+
+        let big = ModInt::new(u64::MAX - 1, u64::MAX);
+        assert_eq!((big + big).value(), u64::MAX - 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "different moduli")]
+    fn modint_rejects_mixed_moduli() {
+        let _ = modint!(1, 7) + modint!(1, 11);
+    }
+
+    #[test]
+    #[should_panic(expected = "greater than 0")]
+    fn modint_rejects_a_zero_modulus_at_runtime() {
+        let modulus = 0;
+        modint!(1, modulus);
+    }
+
+    #[test]
+    fn clock_arithmetic_with_cyclic() {
+        let nine = Cyclic::<12>::new(9);
+        let five = Cyclic::<12>::new(5);
+        assert_eq!((nine + five).value(), 2);
+        assert_eq!((five - nine).value(), 8);
+        assert_eq!((nine * five).value(), 9);
+        assert_eq!(Cyclic::<12>::new(27).value(), 3);
+        assert_eq!(nine.modulus(), 12);
+
+        //5 * 5 = 25 ≅ 1 and 7 * 7 = 49 ≅ 1, so both are their own inverse
+        assert_eq!(five.inverse(), Some(five));
+        assert_eq!(Cyclic::<12>::new(7).inverse().map(|x| x.value()), Some(7));
+        //3 is odd but shares the factor 3 with 12
+        assert_eq!(Cyclic::<12>::new(3).inverse(), None);
+        assert_eq!(Cyclic::<60>::new(7).inverse().map(|x| x.value()), Some(43));
+        assert_eq!(Cyclic::<12>::new(2).pow(5).value(), 8);
+        assert_eq!(nine.to_string(), "9 (mod 12)");
+    }
+}
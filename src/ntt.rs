@@ -0,0 +1,98 @@
+use crate::number_theory::{is_prime, primitive_root};
+use crate::{inverse, mod_mul, mod_pow};
+
+//notice:   A number-theoretic transform of length n works under a prime p and needs a root of unity of order n,
+//          which exists exactly when n divides p - 1. NTT primes such as 998244353 = 119 * 2^23 + 1 are picked so
+//          that p - 1 has a large power of 2 in it. The inverse transform ends by dividing every value by n,
+//          i.e. multiplying by the inverse of n.
+//dev:      This function returns that inverse after checking that prime really is prime and that a transform of
+//          length n is possible under it (n is a power of two dividing prime - 1).
+//returns:  Returns Some(inverse of n Mod prime), or None if the checks fail.
+pub fn ntt_inverse_length(n: u64, prime: u64) -> Option<u64> {
+    if !n.is_power_of_two() || !is_prime(prime) || !(prime - 1).is_multiple_of(n) {
+        return None;
+    }
+
+    inverse(n, prime)
+}
+
+//notice:   If g is a primitive root Mod prime, its powers run through all prime - 1 units, and
+//          g^((prime - 1) / order) has order exactly `order` whenever `order` divides prime - 1.
+//dev:      This function finds a root of unity w of the given order, as used for the butterflies of an NTT.
+//returns:  Returns Some(w) with w^order ≅ 1 and no smaller positive power equal to 1, or None if there is none.
+pub fn primitive_root_of_unity(order: u64, prime: u64) -> Option<u64> {
+    if order == 0 || !is_prime(prime) || !(prime - 1).is_multiple_of(order) {
+        return None;
+    }
+
+    let g = primitive_root(prime)?;
+    Some(mod_pow(g, (prime - 1) / order, prime))
+}
+
+//notice:   The inverse transform uses the twiddle factors w^-k instead of w^k. Since (w^k)^-1 = (w^-1)^k,
+//          they are just the powers of the one inverse w^-1, so no further inverses are needed.
+//dev:      This function lists the inverses of w^0, w^1, ..., w^(n-1) under Mod prime, with one inverse and
+//          n - 1 multiplications. w is normally a root of unity from primitive_root_of_unity, but any unit works.
+//returns:  Returns n entries where entry k is the inverse of w^k, or None if w has no inverse Mod prime.
+pub fn twiddle_inverses(w: u64, n: u64, prime: u64) -> Option<Vec<u64>> {
+    let w_inverse = inverse(w, prime)?;
+    let mut power = 1 % prime;
+
+    Some(
+        (0..n)
+            .map(|_| {
+                let current = power;
+                power = mod_mul(power, w_inverse, prime);
+                current
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ntt_inverse_length_for_998244353() {
+        let p = 998_244_353;
+        for shift in [0, 1, 10, 23] {
+            let n = 1u64 << shift;
+            let n_inverse = ntt_inverse_length(n, p).unwrap();
+            assert_eq!(mod_mul(n, n_inverse, p), 1);
+        }
+        //2^24 doesn't divide p - 1 = 119 * 2^23
+        assert_eq!(ntt_inverse_length(1 << 24, p), None);
+        assert_eq!(ntt_inverse_length(12, p), None);
+        assert_eq!(ntt_inverse_length(4, 1_000_000_005), None);
+        assert_eq!(ntt_inverse_length(4, 0), None);
+    }
+
+    #[test]
+    fn root_of_unity_of_order_8() {
+        let p = 998_244_353;
+        let w = primitive_root_of_unity(8, p).unwrap();
+        assert_eq!(mod_pow(w, 8, p), 1);
+        assert_ne!(mod_pow(w, 4, p), 1);
+        assert_eq!(primitive_root(p), Some(3));
+        assert_eq!(primitive_root_of_unity(1 << 24, p), None);
+        assert_eq!(primitive_root_of_unity(0, p), None);
+    }
+
+    #[test]
+    fn twiddle_inverses_undo_the_powers_of_w() {
+        let p = 998_244_353;
+        let w = primitive_root_of_unity(16, p).unwrap();
+        let inverses = twiddle_inverses(w, 16, p).unwrap();
+        assert_eq!(inverses.len(), 16);
+
+        for (k, &x) in inverses.iter().enumerate() {
+            assert_eq!(mod_mul(mod_pow(w, k as u64, p), x, p), 1);
+            assert_eq!(Some(x), inverse(mod_pow(w, k as u64, p), p));
+        }
+
+        assert_eq!(twiddle_inverses(3, 4, 7), Some(vec![1, 5, 4, 6]));
+        assert_eq!(twiddle_inverses(3, 0, 7), Some(vec![]));
+        assert_eq!(twiddle_inverses(14, 4, 7), None);
+    }
+}
@@ -0,0 +1,935 @@
+use crate::{gcd, inverse, mod_add, mod_mul, mod_pow, units_mod};
+use crate::rng::SplitMix64;
+use std::collections::HashMap;
+
+//notice:   Euler's criterion: for an odd prime p and n not divisible by p, n^((p-1)/2) is 1 Mod p when n is a
+//          square (a quadratic residue) and p - 1 when it isn't.
+//dev:          This function finds a square root of n under Mod p with the Tonelli-Shanks algorithm.
+//              The other square root is always p minus the one returned.
+//Assumption:   Assumes p is prime
+//returns:      Returns Some(r) with r * r ≅ n (mod p), or None if n isn't a quadratic residue.
+pub fn mod_sqrt(n: u64, p: u64) -> Option<u64> {
+    let n = n % p;
+
+    if p == 2 || n == 0 {
+        return Some(n);
+    }
+
+    if mod_pow(n, (p - 1) / 2, p) != 1 {
+        return None;
+    }
+
+    //write p - 1 as q * 2^s with q odd
+    let mut q = p - 1;
+    let mut s = 0;
+    while q.is_multiple_of(2) {
+        q /= 2;
+        s += 1;
+    }
+
+    //any quadratic non-residue z will do. Half of all residues are non-residues, so this ends quickly
+    let mut z = 2;
+    while mod_pow(z, (p - 1) / 2, p) != p - 1 {
+        z += 1;
+    }
+
+    let mut m = s;
+    let mut c = mod_pow(z, q, p);
+    let mut t = mod_pow(n, q, p);
+    let mut r = mod_pow(n, q.div_ceil(2), p);
+
+    //invariant: r^2 = n * t. Each round halves the order of t until t becomes 1
+    while t != 1 {
+        //find the smallest i with t^(2^i) = 1
+        let mut i = 0;
+        let mut power = t;
+        while power != 1 {
+            power = mod_mul(power, power, p);
+            i += 1;
+        }
+
+        let b = mod_pow(c, 1 << (m - i - 1), p);
+        m = i;
+        c = mod_mul(b, b, p);
+        t = mod_mul(t, c, p);
+        r = mod_mul(r, b, p);
+    }
+
+    Some(r)
+}
+
+//notice:   The quadratic formula works under Mod p as well: x = (-b ± sqrt(b^2 - 4ac)) / 2a,
+//          where dividing by 2a means multiplying by its modular multiplicative inverse.
+//          A root only exists when the discriminant b^2 - 4ac has a square root Mod p.
+//dev:          This function solves ax^2 + bx + c ≅ 0 (mod p). When a ≅ 0 it falls back to the linear equation bx + c ≅ 0.
+//              When a, b and c are all ≅ 0 every x is a root, so all p residues are listed. That includes p = 1,
+//              where everything is ≅ 0 and the only root is 0. p = 0 has no residues, so there are no roots.
+//Assumption:   Assumes p is prime or 1. For the all-zero equation the list takes p entries, so p should be small
+//returns:      Returns every root in [0, p) in increasing order, or an empty list if there are none.
+pub fn solve_quadratic_mod(a: u64, b: u64, c: u64, p: u64) -> Vec<u64> {
+    if p == 0 {
+        return Vec::new();
+    }
+
+    let (a, b, c) = (a % p, b % p, c % p);
+    if a == 0 && b == 0 && c == 0 {
+        return (0..p).collect();
+    }
+
+    //2 has no inverse Mod 2, so the formula can't be used. There are only two candidates to try
+    if p == 2 {
+        return (0..2).filter(|&x| (a * x * x + b * x + c) % 2 == 0).collect();
+    }
+
+    if a == 0 {
+        return match inverse(b, p) {
+            Some(inverse_b) => vec![mod_mul(p - c, inverse_b, p)],
+            //b is 0 here, and c isn't since the all-zero equation was handled above, so 0x + c = 0 has no roots
+            None => Vec::new(),
+        };
+    }
+
+    //discriminant = b^2 - 4ac, kept in [0, p)
+    let four_ac = mod_mul(4 % p, mod_mul(a, c, p), p);
+    let discriminant = (mod_mul(b, b, p) + p - four_ac) % p;
+
+    let root = match mod_sqrt(discriminant, p) {
+        Some(root) => root,
+        None => return Vec::new(),
+    };
+
+    //p is an odd prime and a isn't 0, so 2a always has an inverse
+    let inverse_2a = inverse(mod_mul(2, a, p), p).expect("2a is invertible under an odd prime");
+    let minus_b = (p - b) % p;
+
+    let mut roots = vec![
+        mod_mul((minus_b + root) % p, inverse_2a, p),
+        mod_mul((minus_b + p - root) % p, inverse_2a, p),
+    ];
+    roots.sort_unstable();
+    roots.dedup();
+    roots
+}
+
+//notice:   The Fermat numbers are F(k) = 2^(2^k) + 1: 3, 5, 17, 257, 65537, 4294967297, ...
+//          F(0) to F(4) are prime, F(5) = 641 * 6700417 isn't. F(6) is already bigger than a u64.
+//returns:  Returns Some(F(k)) if it fits in a u64, None otherwise.
+pub fn fermat_number(k: u32) -> Option<u64> {
+    if k > 5 {
+        return None;
+    }
+
+    Some((1u64 << (1u64 << k)) + 1)
+}
+
+//notice:   Under Mod F(k), 2^(2^k) ≅ -1. Splitting a into 2^k-bit chunks c0, c1, c2, ... (lowest first) therefore gives
+//          a ≅ c0 - c1 + c2 - ..., so reducing only takes shifts, masks, additions and one subtraction instead of a division.
+//dev:      This function reduces a under Mod F(k) that way. The chunk sums are much smaller than a, so they are
+//          reduced the same way until they fit under F(k).
+fn reduce_mod_fermat(a: u64, k: u32, modulus: u64) -> u64 {
+    if a < modulus {
+        return a;
+    }
+
+    let bits = 1u64 << k;
+    let mask = (1u64 << bits) - 1;
+    let (mut even, mut odd) = (0, 0);
+    let mut rest = a;
+    let mut index = 0;
+
+    while rest > 0 {
+        if index % 2 == 0 {
+            even += rest & mask;
+        } else {
+            odd += rest & mask;
+        }
+        rest >>= bits;
+        index += 1;
+    }
+
+    let even = reduce_mod_fermat(even, k, modulus);
+    let odd = reduce_mod_fermat(odd, k, modulus);
+
+    //even - odd, moved into [0, modulus) by adding the modulus when it would go negative
+    if even >= odd {
+        even - odd
+    } else {
+        even + modulus - odd
+    }
+}
+
+//dev:      This function calculates the inverse of a under Mod F(k), the kth Fermat number, for k in 0..=5.
+//          a is reduced using the structure of F(k) before running the usual Extended Euclidean Algorithm.
+//          F(0) to F(4) are prime, so every a that isn't a multiple of them has an inverse.
+//          F(5) is composite, so multiples of 641 or 6700417 don't.
+//returns:  Returns Some(inverse), or None if k > 5 or a isn't relatively prime to F(k).
+pub fn inverse_fermat_number(a: u64, k: u32) -> Option<u64> {
+    let modulus = fermat_number(k)?;
+    inverse(reduce_mod_fermat(a, k, modulus), modulus)
+}
+
+//notice:   Miller-Rabin: write n - 1 = d * 2^s with d odd. For a prime n and any base a, either a^d ≅ 1 or one of
+//          a^d, a^(2d), ..., a^(2^(s-1) d) is ≅ -1 (mod n). A base where neither happens proves n is composite.
+//          Testing the first twelve primes as bases is enough to make the answer exact for every u64.
+//returns:  Returns true if n is prime, false otherwise.
+pub fn is_prime(n: u64) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+    if n < 2 {
+        return false;
+    }
+
+    for &p in BASES.iter() {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+
+    let mut d = n - 1;
+    let mut s = 0;
+    while d.is_multiple_of(2) {
+        d /= 2;
+        s += 1;
+    }
+
+    BASES.iter().all(|&a| passes_miller_rabin(n, d, s, a))
+}
+
+//dev:      One round of Miller-Rabin for the odd n with n - 1 = d * 2^s, using the base a.
+//returns:  Returns false if a proves n composite, true if n passes the round.
+fn passes_miller_rabin(n: u64, d: u64, s: u32, a: u64) -> bool {
+    let mut x = mod_pow(a, d, n);
+    if x == 1 || x == n - 1 {
+        return true;
+    }
+
+    for _ in 1..s {
+        x = mod_mul(x, x, n);
+        if x == n - 1 {
+            return true;
+        }
+    }
+
+    false
+}
+
+//dev:      This function calculates the inverse of a under Mod m and also reports whether m is prime,
+//          so a caller can annotate the result or pick a prime-only method for later work.
+//returns:  Returns the inverse (None if it doesn't exist) together with true if m is prime.
+pub fn inverse_with_prime_flag(a: u64, m: u64) -> (Option<u64>, bool) {
+    (inverse(a, m), is_prime(m))
+}
+
+//notice:   Generalised Wilson's theorem: the product of all the units Mod n is n - 1 (i.e. -1) when n has a
+//          primitive root (n = 1, 2, 4, p^k or 2p^k for an odd prime p), and 1 for every other n.
+//          For a prime p this is Wilson's theorem, (p - 1)! ≅ -1 (mod p).
+//          The reason is that every unit pairs up with its inverse and each pair multiplies to 1,
+//          so only the units that are their own inverse are left over.
+//dev:          This function multiplies all the units of n together.
+//Assumption:   Assumes n > 0
+//returns:      Returns the product of the units of n, reduced under Mod n.
+pub fn product_of_units(n: u64) -> u64 {
+    units_mod(n).into_iter().fold(1 % n, |product, unit| mod_mul(product, unit, n))
+}
+
+//notice:   Taking inverses just shuffles the units of m, so the inverses add up to the same as the units themselves.
+//          For m > 2 the units pair up as a and m - a, each pair adds up to m, so the sum is 0 Mod m.
+//          Example: the units Mod 8 are 1, 3, 5, 7, each its own inverse, and 1 + 3 + 5 + 7 = 16 ≅ 0.
+//dev:          This function adds up the inverses of all the units of m one by one, without relying on the identity.
+//Assumption:   Assumes m > 0
+//returns:      Returns the sum of the inverses of the units of m, reduced under Mod m. It is 1 for m = 2 and 0 otherwise.
+pub fn sum_of_inverses(m: u64) -> u64 {
+    units_mod(m)
+        .into_iter()
+        .filter_map(|unit| inverse(unit, m))
+        .fold(0, |sum, unit_inverse| mod_add(sum, unit_inverse, m))
+}
+
+//dev:      This function splits n into its prime factors. Small factors are found by trial division,
+//          and whatever is left is split with Pollard's rho algorithm, so even a 64 bit n is factored quickly.
+//returns:  Returns the (prime, exponent) pairs with the primes in increasing order. 0 and 1 give an empty list.
+pub fn factorize(n: u64) -> Vec<(u64, u32)> {
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let mut primes = Vec::new();
+    let mut rest = n;
+    let mut p = 2;
+    while p < 1000 && p * p <= rest {
+        while rest.is_multiple_of(p) {
+            primes.push(p);
+            rest /= p;
+        }
+        p += 1;
+    }
+    split_into_primes(rest, &mut primes);
+
+    primes.sort_unstable();
+    let mut factors: Vec<(u64, u32)> = Vec::new();
+    for prime in primes {
+        match factors.last_mut() {
+            Some((last, exponent)) if *last == prime => *exponent += 1,
+            _ => factors.push((prime, 1)),
+        }
+    }
+
+    factors
+}
+
+//dev:      Pushes the prime factors of n (with repeats, in no particular order) onto primes.
+fn split_into_primes(n: u64, primes: &mut Vec<u64>) {
+    if n == 1 {
+        return;
+    }
+
+    if is_prime(n) {
+        primes.push(n);
+        return;
+    }
+
+    let divisor = pollard_rho(n);
+    split_into_primes(divisor, primes);
+    split_into_primes(n / divisor, primes);
+}
+
+//notice:   Pollard's rho walks the sequence x -> x^2 + c (mod n). Modulo an unknown prime factor p of n the
+//          sequence must repeat after about sqrt(p) steps, and when it does, gcd(|x - y|, n) reveals p.
+//dev:          This function finds a non-trivial divisor of n, trying a new constant c whenever a walk fails.
+//Assumption:   Assumes n is composite
+fn pollard_rho(n: u64) -> u64 {
+    if n.is_multiple_of(2) {
+        return 2;
+    }
+
+    for c in 1.. {
+        //x^2 + c, written so that adding c can't overflow when n is close to u64::MAX
+        let step = |x: u64| {
+            let square = mod_mul(x, x, n);
+            if square >= n - c { square - (n - c) } else { square + c }
+        };
+        //the tortoise x moves one step at a time and the hare y moves two, until they meet modulo p
+        let (mut x, mut y, mut d) = (2, 2, 1);
+
+        while d == 1 {
+            x = step(x);
+            y = step(step(y));
+            d = gcd(x.abs_diff(y), n);
+        }
+
+        if d != n {
+            return d;
+        }
+    }
+
+    unreachable!("every composite number has a divisor")
+}
+
+//notice:   Euler's totient φ(n) counts the units Mod n. For n = p1^k1 * ... * pr^kr it equals
+//          n * (1 - 1/p1) * ... * (1 - 1/pr).
+//returns:  Returns φ(n). φ(0) is taken to be 0.
+pub fn euler_totient(n: u64) -> u64 {
+    factorize(n).into_iter().fold(n, |phi, (p, _)| phi / p * (p - 1))
+}
+
+//notice:   For a prime p ≅ 3 (mod 4), a quadratic residue a has the square root a^((p+1)/4), and its inverse a^-1 is
+//          a quadratic residue too, with the square root s = a^((p-3)/4). Squaring s gives a^((p-3)/2), and by Euler's
+//          criterion a^((p-1)/2) ≅ 1, so a^((p-3)/2) * a ≅ 1. In other words, the inverse is the square of a square root.
+//          This is a roundabout way to get an inverse, and a useful cross-check of the Extended Euclidean result.
+//dev:          This function calculates the inverse of a quadratic residue a under Mod p that way.
+//Assumption:   Assumes p is prime
+//returns:      Returns Some(inverse), or None if p isn't 3 Mod 4 or a isn't a non-zero quadratic residue Mod p.
+pub fn inverse_via_sqrt(a: u64, p: u64) -> Option<u64> {
+    let a = a % p;
+
+    if p % 4 != 3 || a == 0 || mod_pow(a, (p - 1) / 2, p) != 1 {
+        return None;
+    }
+
+    let root_of_inverse = mod_pow(a, (p - 3) / 4, p);
+    Some(mod_mul(root_of_inverse, root_of_inverse, p))
+}
+
+//notice:   The multiplicative order of a Mod m is the smallest k > 0 with a^k ≅ 1 (mod m).
+//          By Euler's theorem a^φ(m) ≅ 1, so the order always divides φ(m).
+//dev:      This function starts from k = φ(m) and divides out each prime factor of k for as long as a^k stays 1.
+//returns:  Returns Some(order), or None if a isn't relatively prime to m (or m is 0).
+pub fn multiplicative_order(a: u64, m: u64) -> Option<u64> {
+    if m == 0 || gcd(a, m) != 1 {
+        return None;
+    }
+
+    let phi = euler_totient(m);
+    let mut order = phi;
+
+    for (p, _) in factorize(phi) {
+        while order.is_multiple_of(p) && mod_pow(a, order / p, m) == 1 % m {
+            order /= p;
+        }
+    }
+
+    Some(order)
+}
+
+//notice:   a is a primitive root Mod m when its order is as large as possible, φ(m).
+//          Its powers then run through every unit of m. Example: the powers of 3 Mod 7 are 3, 2, 6, 4, 5, 1,
+//          but the powers of 2 Mod 7 are only 2, 4, 1.
+//returns:  Returns true if the order of a Mod m is φ(m), false otherwise (including when a has no order).
+pub fn has_maximal_order(a: u64, m: u64) -> bool {
+    multiplicative_order(a, m) == Some(euler_totient(m))
+}
+
+//dev:      This function lists the divisors of n. They are built from the prime factorisation of n,
+//          by multiplying together every combination of prime powers, rather than by trying every number up to n.
+//          n = p1^e1 * ... * pr^er has (e1 + 1) * ... * (er + 1) divisors.
+//returns:  Returns the divisors in increasing order. 0 gives an empty list and 1 gives [1].
+pub fn divisors(n: u64) -> Vec<u64> {
+    if n == 0 {
+        return Vec::new();
+    }
+
+    //every divisor is a product p1^k1 * ... * pr^kr with each ki between 0 and ei
+    let mut divisors = vec![1];
+    for (p, exponent) in factorize(n) {
+        let mut next = Vec::with_capacity(divisors.len() * (exponent as usize + 1));
+        for &d in &divisors {
+            let mut power = d;
+            next.push(power);
+            for _ in 0..exponent {
+                power *= p;
+                next.push(power);
+            }
+        }
+        divisors = next;
+    }
+
+    divisors.sort_unstable();
+    divisors
+}
+
+//dev:      This function calculates the inverse of a under every divisor d of n.
+//returns:  Returns (d, inverse of a Mod d) for every divisor d in increasing order. The inverse is None where it doesn't exist.
+pub fn inverse_over_divisors(a: u64, n: u64) -> Vec<(u64, Option<u64>)> {
+    divisors(n).into_iter().map(|d| (d, inverse(a, d))).collect()
+}
+
+//notice:   τ(n) counts the divisors of n. For n = p1^e1 * ... * pr^er it is (e1 + 1) * ... * (er + 1),
+//          because each divisor picks one of e + 1 powers of every prime.
+//returns:  Returns τ(n). τ(0) is taken to be 0.
+pub fn num_divisors(n: u64) -> u64 {
+    if n == 0 {
+        return 0;
+    }
+
+    factorize(n).into_iter().map(|(_, exponent)| exponent as u64 + 1).product()
+}
+
+//notice:   σ(n) adds up the divisors of n. Like τ and φ it is multiplicative, and for a prime power
+//          σ(p^e) = 1 + p + p^2 + ... + p^e, so σ(n) is the product of these sums over the primes of n.
+//dev:      The sums are built in a u128, since σ(n) can be several times bigger than n and overflow a u64.
+//returns:  Returns σ(n). σ(0) is taken to be 0. Panics if σ(n) doesn't fit in a u64.
+pub fn sum_divisors(n: u64) -> u64 {
+    if n == 0 {
+        return 0;
+    }
+
+    let sigma: u128 = factorize(n)
+        .into_iter()
+        .map(|(p, exponent)| {
+            let mut power: u128 = 1;
+            let mut sum: u128 = 1;
+            for _ in 0..exponent {
+                power *= p as u128;
+                sum += power;
+            }
+            sum
+        })
+        .product();
+
+    u64::try_from(sigma).unwrap_or_else(|_| panic!("σ({}) doesn't fit in a u64", n))
+}
+
+//notice:   Only 1, 2, 4, p^k and 2p^k (p an odd prime) have primitive roots. For any other m every unit has an
+//          order smaller than φ(m), so searching for a primitive root would never succeed.
+//returns:  Returns true if m has a primitive root, false otherwise (including m = 0).
+pub fn has_primitive_root(m: u64) -> bool {
+    match m {
+        0 => false,
+        1 | 2 | 4 => true,
+        _ => {
+            //strip a single factor of 2, what is left has to be a power of one odd prime
+            let odd_part = if m % 4 == 2 { m / 2 } else { m };
+            let factors = factorize(odd_part);
+            factors.len() == 1 && factors[0].0 != 2
+        }
+    }
+}
+
+//dev:      This function finds the smallest primitive root of m: a unit whose order is φ(m).
+//          A unit g has order φ(m) exactly when g^(φ(m)/q) isn't 1 for any prime q dividing φ(m).
+//          has_primitive_root is checked first, so moduli without one return straight away instead of searching.
+//returns:  Returns Some(g), or None if m has no primitive root.
+pub fn primitive_root(m: u64) -> Option<u64> {
+    if !has_primitive_root(m) {
+        return None;
+    }
+
+    let phi = euler_totient(m);
+    let primes: Vec<u64> = factorize(phi).into_iter().map(|(q, _)| q).collect();
+
+    (0..m).find(|&g| gcd(g, m) == 1 && primes.iter().all(|&q| mod_pow(g, phi / q, m) != 1))
+}
+
+//notice:   The discrete logarithm of h to the base g Mod m is the exponent x with g^x ≅ h.
+//          Example: the powers of 3 Mod 7 are 3, 2, 6, 4, 5, 1, so the discrete log of 4 to the base 3 is 4.
+//dev:      This function finds x with the baby-step giant-step algorithm. With n the order of g and s = ceil(sqrt(n)),
+//          every x below n can be written as i * s + j with i, j < s. The baby steps store g^j in a table,
+//          and the giant steps multiply h by g^-s over and over, looking each result up in the table.
+//          This takes about 2 * sqrt(n) multiplications, plus a table of sqrt(n) entries.
+//returns:  Returns the smallest x >= 0 with g^x ≅ h (mod m), or None if h isn't a power of g or g has no inverse.
+pub fn discrete_log(g: u64, h: u64, m: u64) -> Option<u64> {
+    let order = multiplicative_order(g, m)?;
+    let h = h % m;
+    let steps = order.isqrt() + u64::from(order.isqrt().pow(2) != order);
+
+    //baby steps: g^j -> j, keeping the smallest j for every power
+    let mut baby_steps = HashMap::with_capacity(steps as usize);
+    let mut power = 1 % m;
+    for j in 0..steps {
+        baby_steps.entry(power).or_insert(j);
+        power = mod_mul(power, g, m);
+    }
+
+    //giant steps: h * g^(-s*i) for i = 0, 1, ...
+    let giant_step = inverse(mod_pow(g, steps, m), m)?;
+    let mut gamma = h;
+    for i in 0..steps {
+        if let Some(&j) = baby_steps.get(&gamma) {
+            return Some(i * steps + j);
+        }
+        gamma = mod_mul(gamma, giant_step, m);
+    }
+
+    None
+}
+
+//notice:   If g^x ≅ a then g^(-x) ≅ a^-1, so the discrete logs of a and of its inverse add up to a multiple of the
+//          order of g. For a primitive root g of a prime p that order is p - 1, so the two logs add up to p - 1,
+//          except for a = 1 where both are 0. Example: with g = 3 and p = 7, the log of 2 is 2 and the log of
+//          its inverse 4 is 4, and 2 + 4 = 6.
+//dev:      This function finds the discrete logs of a and of inverse(a, p), and checks that they add up to p - 1.
+//returns:  Returns Some((log of a, log of the inverse of a)) to the base g, or None if a has no inverse,
+//          g isn't a primitive root mod p (so p isn't prime either), or the logs don't add up.
+pub fn inverse_log_relationship(a: u64, g: u64, p: u64) -> Option<(u64, u64)> {
+    if p < 2 || multiplicative_order(g, p)? != p - 1 {
+        return None;
+    }
+
+    let a_inverse = inverse(a, p)?;
+    let log_a = discrete_log(g, a, p)?;
+    let log_inverse = discrete_log(g, a_inverse, p)?;
+
+    let adds_up = (log_a == 0 && log_inverse == 0) || log_a + log_inverse == p - 1;
+    if !adds_up {
+        return None;
+    }
+
+    Some((log_a, log_inverse))
+}
+
+//notice:   a has an inverse under a prime p exactly when p doesn't divide a. Only a few primes can divide a u64 at once
+//          (the product of the first 16 primes is already above 2^64), so the answer is never larger than 53.
+//          Example: 4 = 2 * 2, so 2 doesn't work but 3 does. 30 = 2 * 3 * 5, so the answer is 7.
+//dev:      This function finds the smallest prime modulus under which a is invertible,
+//          by testing the primes in increasing order.
+//          Panics if a is 0, because every prime divides 0.
+//returns:  Returns the smallest prime p that doesn't divide a.
+pub fn next_prime_invertible(a: u64) -> u64 {
+    assert!(a != 0, "0 has no inverse under any prime");
+
+    (2..).filter(|&p| is_prime(p)).find(|&p| !a.is_multiple_of(p)).unwrap()
+}
+
+//dev:      This function pairs up the two questions a Diffie-Hellman style example usually asks about a:
+//          what undoes multiplying by it (its inverse), and which power of g it is (its discrete log).
+//returns:  Returns (inverse(a, p), discrete_log(g, a, p)), either of which can be None on its own.
+pub fn inverse_and_dlog(a: u64, g: u64, p: u64) -> (Option<u64>, Option<u64>) {
+    (inverse(a, p), discrete_log(g, a, p))
+}
+
+//notice:   The inverse from Fermat's little theorem, a^(p-2), is only right when p really is prime. With random bases,
+//          Miller-Rabin lets a composite number through a round at most 1/4 of the time, so after k rounds
+//          p is prime with probability at least 1 - 4^-k.
+//dev:      This function runs rounds of Miller-Rabin with seeded random bases in [2, p - 2] (unlike is_prime, which
+//          uses fixed bases and is exact), then calculates the inverse of a with Fermat's little theorem.
+//          p below 5 is too small to pick a base from, so it is checked exactly.
+//returns:  Returns (Some(a^(p-2) mod p), 1 - 4^-rounds) if p passes every round and a isn't a multiple of p.
+//          A p that is shown to be composite gives (None, 0.0), and so does rounds = 0, which gives no evidence
+//          either way. With a = 0 Mod p the inverse is None.
+pub fn inverse_prime_probabilistic(a: u64, p: u64, rounds: usize, seed: u64) -> (Option<u64>, f64) {
+    let probability = if p < 5 {
+        if is_prime(p) { 1.0 } else { 0.0 }
+    } else if p.is_multiple_of(2) {
+        0.0
+    } else {
+        let (mut d, mut s) = (p - 1, 0);
+        while d.is_multiple_of(2) {
+            d /= 2;
+            s += 1;
+        }
+
+        let mut rng = SplitMix64(seed);
+        if (0..rounds).all(|_| passes_miller_rabin(p, d, s, 2 + rng.next() % (p - 3))) {
+            1.0 - 0.25f64.powi(rounds as i32)
+        } else {
+            0.0
+        }
+    };
+
+    if probability == 0.0 || a.is_multiple_of(p) {
+        return (None, probability);
+    }
+
+    (Some(mod_pow(a, p - 2, p)), probability)
+}
+
+//notice:   ω(m) counts the distinct primes dividing m: ω(12) = 2 because 12 = 2^2 * 3. By the Chinese Remainder
+//          Theorem, the units of m split into ω(m) independent pieces, one per prime power, so ω(m) is a rough
+//          measure of how much structure the modulus has.
+//dev:      This function calculates the inverse of a under Mod m and also reports ω(m), counted from factorize(m).
+//returns:  Returns (Some(inverse) or None, ω(m)). ω(0) and ω(1) are both 0.
+pub fn inverse_with_omega(a: u64, m: u64) -> (Option<u64>, u32) {
+    (inverse(a, m), factorize(m).len() as u32)
+}
+
+//notice:   The inverse and the order of a are tied together: a^k ≅ 1 for k the order, so a * a^(k-1) ≅ 1 and the
+//          inverse is a^(k-1). Example: 2^3 = 8 ≅ 1 Mod 7, so the order of 2 is 3 and its inverse is 2^2 = 4.
+//dev:      This function reports both for a under Mod m, for callers looking at an element as a whole.
+//returns:  Returns (Some(inverse), Some(order)), or (None, None) if a has no inverse under Mod m.
+pub fn inverse_and_order(a: u64, m: u64) -> (Option<u64>, Option<u64>) {
+    match inverse(a, m) {
+        Some(x) => (Some(x), multiplicative_order(a, m)),
+        None => (None, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solve_quadratic_mod_finds_every_root() {
+        //x^2 - 1 = (x - 1)(x + 1)
+        assert_eq!(solve_quadratic_mod(1, 0, 6, 7), vec![1, 6]);
+        //x^2 + 1 has no root Mod 7, since -1 isn't a square
+        assert_eq!(solve_quadratic_mod(1, 0, 1, 7), Vec::<u64>::new());
+        //(x - 3)^2 has the double root 3
+        assert_eq!(solve_quadratic_mod(1, 11, 9, 17), vec![3]);
+        //a = 0 leaves 2x + 3 = 0, so x = 2
+        assert_eq!(solve_quadratic_mod(0, 2, 3, 7), vec![2]);
+        assert_eq!(solve_quadratic_mod(0, 0, 3, 7), Vec::<u64>::new());
+        assert_eq!(solve_quadratic_mod(1, 1, 0, 2), vec![0, 1]);
+        for x in solve_quadratic_mod(3, 5, 1_000_000_000, 1_000_000_007) {
+            assert_eq!((3 * mod_mul(x, x, 1_000_000_007) + 5 * x + 1_000_000_000) % 1_000_000_007, 0);
+        }
+    }
+
+    #[test]
+    fn solve_quadratic_mod_edge_moduli_and_the_zero_equation() {
+        //every x solves 0x^2 + 0x + 0 = 0, and the coefficients here are all multiples of 7
+        assert_eq!(solve_quadratic_mod(7, 0, 14, 7), vec![0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(solve_quadratic_mod(0, 0, 0, 2), vec![0, 1]);
+        //Mod 1 every equation is the zero equation, with the single root 0
+        assert_eq!(solve_quadratic_mod(1, 2, 3, 1), vec![0]);
+        assert_eq!(solve_quadratic_mod(0, 0, 0, 1), vec![0]);
+        assert_eq!(solve_quadratic_mod(1, 2, 3, 0), Vec::<u64>::new());
+        assert_eq!(solve_quadratic_mod(0, 0, 0, 0), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn inverse_log_relationship_needs_a_primitive_root() {
+        assert_eq!(inverse_log_relationship(2, 3, 7), Some((2, 4)));
+        assert_eq!(inverse_log_relationship(1, 3, 7), Some((0, 0)));
+        //2 has order 3 Mod 7, so it isn't a primitive root
+        assert_eq!(inverse_log_relationship(4, 2, 7), None);
+        //Mod 9 no number has order 8, so nothing is accepted as a base
+        assert_eq!(inverse_log_relationship(4, 2, 9), None);
+        assert_eq!(inverse_log_relationship(0, 3, 7), None);
+        assert_eq!(inverse_log_relationship(5, 3, 1), None);
+    }
+
+    #[test]
+    fn mod_sqrt_squares_back() {
+        //41 - 1 = 5 * 2^3, so Tonelli-Shanks runs more than one round here
+        for &p in &[3u64, 5, 7, 13, 17, 41, 1_000_000_007, 998_244_353] {
+            for n in 0..60 {
+                match mod_sqrt(n, p) {
+                    Some(r) => assert_eq!(mod_mul(r, r, p), n % p, "n = {}, p = {}", n, p),
+                    None => assert_eq!(mod_pow(n, (p - 1) / 2, p), p - 1),
+                }
+            }
+        }
+        assert_eq!(mod_sqrt(3, 7), None);
+        assert_eq!(mod_sqrt(1, 2), Some(1));
+    }
+
+    #[test]
+    fn inverse_fermat_number_matches_the_general_inverse() {
+        assert_eq!(fermat_number(4), Some(65537));
+        assert_eq!(fermat_number(6), None);
+        for a in (0..200_000).step_by(97).chain([u64::MAX, u64::MAX - 65537]) {
+            assert_eq!(inverse_fermat_number(a, 4), inverse(a, 65537), "a = {}", a);
+            assert_eq!(reduce_mod_fermat(a, 4, 65537), a % 65537);
+        }
+        //F(5) = 641 * 6700417
+        assert_eq!(inverse_fermat_number(641 * 3, 5), None);
+        assert_eq!(inverse_fermat_number(u64::MAX, 5), inverse(u64::MAX, 4294967297));
+        assert_eq!(inverse_fermat_number(3, 6), None);
+    }
+
+    #[test]
+    fn is_prime_matches_trial_division() {
+        let trial = |n: u64| n >= 2 && (2..).take_while(|d| d * d <= n).all(|d| !n.is_multiple_of(d));
+        for n in 0..3000 {
+            assert_eq!(is_prime(n), trial(n), "n = {}", n);
+        }
+        assert!(is_prime(18446744073709551557));
+        //strong pseudoprimes to several small bases
+        assert!(!is_prime(3215031751));
+        assert!(!is_prime(3825123056546413051));
+        assert!(!is_prime(u64::MAX));
+    }
+
+    #[test]
+    fn inverse_with_prime_flag_reports_both() {
+        assert_eq!(inverse_with_prime_flag(3, 7), (Some(5), true));
+        assert_eq!(inverse_with_prime_flag(3, 10), (Some(7), false));
+        assert_eq!(inverse_with_prime_flag(4, 10), (None, false));
+    }
+
+    #[test]
+    fn product_of_units_follows_wilson() {
+        for p in [2u64, 3, 5, 7, 11, 13, 101] {
+            assert_eq!(product_of_units(p), p - 1);
+        }
+        assert_eq!(product_of_units(8), 1);
+        assert_eq!(product_of_units(12), 1);
+        assert_eq!(product_of_units(15), 1);
+        //4, 9 and 18 have primitive roots, so the product is -1 again
+        assert_eq!(product_of_units(4), 3);
+        assert_eq!(product_of_units(9), 8);
+        assert_eq!(product_of_units(18), 17);
+        assert_eq!(product_of_units(1), 0);
+    }
+
+    #[test]
+    fn factorize_and_euler_totient() {
+        assert_eq!(factorize(360), vec![(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(factorize(1), Vec::new());
+        assert_eq!(factorize(0), Vec::new());
+        assert_eq!(factorize(18446744073709551557), vec![(18446744073709551557, 1)]);
+        //two primes past the trial division limit, so Pollard's rho has to split them
+        assert_eq!(factorize(1_000_000_007 * 998_244_353), vec![(998_244_353, 1), (1_000_000_007, 1)]);
+        assert_eq!(factorize(u64::MAX), vec![(3, 1), (5, 1), (17, 1), (257, 1), (641, 1), (65537, 1), (6700417, 1)]);
+
+        for n in 1..300u64 {
+            let units = (0..n).filter(|&a| gcd(a, n) == 1).count() as u64;
+            assert_eq!(euler_totient(n), units, "n = {}", n);
+            assert_eq!(factorize(n).iter().map(|&(p, k)| p.pow(k)).product::<u64>(), n);
+        }
+        assert_eq!(euler_totient(0), 0);
+    }
+
+    #[test]
+    fn inverse_via_sqrt_matches_the_standard_inverse() {
+        for &p in &[7u64, 11, 19, 1_000_000_007] {
+            for a in 1..200 {
+                let expected = if mod_sqrt(a, p).is_some() && a % p != 0 { inverse(a, p) } else { None };
+                assert_eq!(inverse_via_sqrt(a, p), expected, "a = {}, p = {}", a, p);
+            }
+        }
+        //3 isn't a square Mod 7
+        assert_eq!(inverse_via_sqrt(3, 7), None);
+        //13 is 1 Mod 4, so the method doesn't apply
+        assert_eq!(inverse_via_sqrt(4, 13), None);
+        assert_eq!(inverse_via_sqrt(0, 7), None);
+    }
+
+    #[test]
+    fn multiplicative_order_and_maximal_order() {
+        assert!(has_maximal_order(3, 7));
+        //2 has order 3 Mod 7: 2, 4, 1
+        assert_eq!(multiplicative_order(2, 7), Some(3));
+        assert!(!has_maximal_order(2, 7));
+        assert!(!has_maximal_order(4, 7));
+        assert!(has_maximal_order(5, 7));
+        assert_eq!(multiplicative_order(4, 10), None);
+        assert_eq!(multiplicative_order(3, 1_000_000_007), Some(500_000_003));
+        assert!(has_maximal_order(5, 1_000_000_007));
+        //Mod 8 every unit has order at most 2, below φ(8) = 4
+        assert!((1..8).all(|a| !has_maximal_order(a, 8)));
+    }
+
+    #[test]
+    fn inverse_over_divisors_of_twelve() {
+        assert_eq!(
+            inverse_over_divisors(5, 12),
+            vec![(1, Some(0)), (2, Some(1)), (3, Some(2)), (4, Some(1)), (6, Some(5)), (12, Some(5))]
+        );
+        assert_eq!(inverse_over_divisors(2, 12)[1..4], [(2, None), (3, Some(2)), (4, None)]);
+    }
+
+    #[test]
+    fn divisors_come_from_the_factorisation() {
+        assert_eq!(divisors(12), vec![1, 2, 3, 4, 6, 12]);
+        assert_eq!(divisors(1), vec![1]);
+        assert!(divisors(0).is_empty());
+        for n in 1..500u64 {
+            let expected: u64 = factorize(n).iter().map(|&(_, k)| k as u64 + 1).product();
+            let list = divisors(n);
+            assert_eq!(list.len() as u64, expected);
+            assert_eq!(list, (1..=n).filter(|d| n.is_multiple_of(*d)).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn divisor_count_and_sum() {
+        assert_eq!(num_divisors(12), 6);
+        assert_eq!(sum_divisors(12), 28);
+        assert_eq!(num_divisors(1), 1);
+        assert_eq!(sum_divisors(1), 1);
+        //28 is perfect: its divisors other than itself add up to 28
+        assert_eq!(sum_divisors(28), 56);
+        for n in 1..300u64 {
+            assert_eq!(num_divisors(n), divisors(n).len() as u64);
+            assert_eq!(sum_divisors(n), divisors(n).iter().sum::<u64>());
+        }
+    }
+
+    #[test]
+    fn has_primitive_root_for_1_2_4_pk_2pk() {
+        for m in [1, 2, 4, 9, 14, 27, 50, 1_000_000_007] {
+            assert!(has_primitive_root(m), "m = {}", m);
+        }
+        for m in [0, 8, 12, 15, 16, 21, 36] {
+            assert!(!has_primitive_root(m), "m = {}", m);
+        }
+        //the definition: some unit has order φ(m)
+        for m in 1..200 {
+            let maximal = (0..m).any(|a| has_maximal_order(a, m));
+            assert_eq!(has_primitive_root(m), maximal, "m = {}", m);
+        }
+    }
+
+    #[test]
+    fn primitive_root_generates_every_unit() {
+        assert_eq!(primitive_root(7), Some(3));
+        assert_eq!(primitive_root(1), Some(0));
+        assert_eq!(primitive_root(8), None);
+        for m in [2u64, 4, 9, 14, 50, 101] {
+            let g = primitive_root(m).unwrap();
+            assert_eq!(multiplicative_order(g, m), Some(euler_totient(m)), "m = {}", m);
+        }
+    }
+
+    #[test]
+    fn sum_of_inverses_is_zero_above_two() {
+        for m in [3u64, 4, 8, 10, 12, 97, 360] {
+            assert_eq!(sum_of_inverses(m), 0, "m = {}", m);
+        }
+        assert_eq!(sum_of_inverses(2), 1);
+        assert_eq!(sum_of_inverses(1), 0);
+    }
+
+    #[test]
+    fn discrete_log_finds_the_smallest_exponent() {
+        assert_eq!(discrete_log(3, 2, 7), Some(2));
+        assert_eq!(discrete_log(3, 1, 7), Some(0));
+        //the powers of 2 Mod 7 are 1, 2 and 4, so 3 has no log
+        assert_eq!(discrete_log(2, 4, 7), Some(2));
+        assert_eq!(discrete_log(2, 3, 7), None);
+        let p = 1_000_000_007;
+        for x in [0u64, 1, 12345, 500_000_002] {
+            assert_eq!(discrete_log(5, mod_pow(5, x, p), p), Some(x));
+        }
+        assert_eq!(discrete_log(2, 3, 10), None);
+    }
+
+    #[test]
+    fn next_prime_invertible_skips_the_prime_factors() {
+        assert_eq!(next_prime_invertible(1), 2);
+        assert_eq!(next_prime_invertible(4), 3);
+        assert_eq!(next_prime_invertible(30), 7);
+        assert_eq!(next_prime_invertible(7), 2);
+        //2 * 3 * 5 * ... * 47, the product of the first 15 primes
+        assert_eq!(next_prime_invertible(614_889_782_588_491_410), 53);
+
+        for a in [4, 30, 97, 1000, u64::MAX] {
+            let p = next_prime_invertible(a);
+            assert!(is_prime(p));
+            assert_eq!(mod_mul(a, inverse(a, p).unwrap(), p), 1);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "0 has no inverse under any prime")]
+    fn next_prime_invertible_rejects_0() {
+        next_prime_invertible(0);
+    }
+
+    #[test]
+    fn inverse_and_dlog_under_a_small_prime() {
+        //the powers of 3 Mod 7 are 1, 3, 2, 6, 4, 5
+        assert_eq!(inverse_and_dlog(6, 3, 7), (Some(6), Some(3)));
+        assert_eq!(inverse_and_dlog(5, 3, 7), (Some(3), Some(5)));
+        assert_eq!(inverse_and_dlog(1, 3, 7), (Some(1), Some(0)));
+        //2 only reaches 1, 2 and 4
+        assert_eq!(inverse_and_dlog(3, 2, 7), (Some(5), None));
+        assert_eq!(inverse_and_dlog(0, 3, 7), (None, None));
+    }
+
+    #[test]
+    fn inverse_prime_probabilistic_reports_the_miller_rabin_confidence() {
+        let p = 1_000_000_007;
+        for rounds in [1, 5, 20] {
+            let (x, probability) = inverse_prime_probabilistic(3, p, rounds, 291);
+            assert_eq!(x, Some(333_333_336));
+            assert_eq!(probability, 1.0 - 0.25f64.powi(rounds as i32));
+        }
+
+        assert_eq!(inverse_prime_probabilistic(p, p, 10, 291), (None, 1.0 - 0.25f64.powi(10)));
+        assert_eq!(inverse_prime_probabilistic(3, p, 0, 291), (None, 0.0));
+        //561 = 3 * 11 * 17 is a Carmichael number, which fools Fermat's test but not Miller-Rabin
+        assert_eq!(inverse_prime_probabilistic(2, 561, 20, 291), (None, 0.0));
+        assert_eq!(inverse_prime_probabilistic(2, 1 << 20, 5, 291), (None, 0.0));
+        assert_eq!(inverse_prime_probabilistic(2, 3, 5, 291), (Some(2), 1.0));
+        assert_eq!(inverse_prime_probabilistic(2, 4, 5, 291), (None, 0.0));
+    }
+
+    #[test]
+    fn inverse_with_omega_counts_distinct_primes() {
+        assert_eq!(inverse_with_omega(7, 97), (Some(14), 1));
+        //243 = 3^5
+        assert_eq!(inverse_with_omega(2, 243), (Some(122), 1));
+        //30 = 2 * 3 * 5
+        assert_eq!(inverse_with_omega(7, 30), (Some(13), 3));
+        assert_eq!(inverse_with_omega(6, 30), (None, 3));
+        assert_eq!(inverse_with_omega(5, 1), (Some(0), 0));
+        assert_eq!(inverse_with_omega(5, 0), (None, 0));
+    }
+
+    #[test]
+    fn inverse_and_order_of_2_mod_7() {
+        //2^3 = 8 ≅ 1, and the inverse 4 is 2^2
+        assert_eq!(inverse_and_order(2, 7), (Some(4), Some(3)));
+        assert_eq!(inverse_and_order(3, 7), (Some(5), Some(6)));
+        assert_eq!(inverse_and_order(1, 7), (Some(1), Some(1)));
+        assert_eq!(inverse_and_order(4, 10), (None, None));
+        assert_eq!(inverse_and_order(3, 0), (None, None));
+
+        for a in [3, 7, 11, 99] {
+            let (x, order) = inverse_and_order(a, 100);
+            assert_eq!(x, Some(mod_pow(a, order.unwrap() - 1, 100)));
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use std::fmt;
+
+//dev:      A multiplication that didn't fit in an i128.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OverflowError {
+    pub a: i128,
+    pub b: i128,
+}
+
+impl fmt::Display for OverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} * {} overflows an i128", self.a, self.b)
+    }
+}
+
+impl std::error::Error for OverflowError {}
+
+//dev:      Decides what happens when a multiplication inside the inverse loop overflows.
+//          The policy is picked through a type parameter, so the choice costs nothing at runtime.
+pub trait OverflowPolicy {
+    fn mul(a: i128, b: i128) -> Result<i128, OverflowError>;
+}
+
+//dev:      Halts the program on overflow, like the panic in modular_multiplicative_inverse.
+//          Unlike a plain *, this also panics in release builds.
+pub struct Panicking;
+
+//dev:      Reports overflow as an OverflowError.
+pub struct Checked;
+
+//dev:      Lets the product wrap around silently, like the plain * of a release build.
+pub struct Wrapping;
+
+impl OverflowPolicy for Panicking {
+    fn mul(a: i128, b: i128) -> Result<i128, OverflowError> {
+        match a.checked_mul(b) {
+            Some(product) => Ok(product),
+            None => panic!("{}", OverflowError { a, b }),
+        }
+    }
+}
+
+impl OverflowPolicy for Checked {
+    fn mul(a: i128, b: i128) -> Result<i128, OverflowError> {
+        a.checked_mul(b).ok_or(OverflowError { a, b })
+    }
+}
+
+impl OverflowPolicy for Wrapping {
+    fn mul(a: i128, b: i128) -> Result<i128, OverflowError> {
+        Ok(a.wrapping_mul(b))
+    }
+}
+
+//notice:   The coefficients of the table stay within m / 2 in size on every row but the last, whose t is ±m / GCD(a, m).
+//          For a u128 modulus past i128::MAX that last t no longer fits in an i128, so the multiplication y * q
+//          that leads to it can overflow. The last t is never read (x already holds the inverse by then), so the
+//          overflow does no damage by itself, and it is up to the policy what to make of it.
+//          Example: Mod 2^128 - 1, the inverse of 2 is 2^127, and the last row multiplies -(2^127 - 1) by 2.
+//dev:      This function is the Extended Euclidean loop on u128 inputs with i128 coefficients, where every y * q
+//          multiplication goes through the policy P, e.g. inverse_with_policy::<Checked>(3, 5).
+//          Panicking panics on that overflow, Checked returns it as an error, and Wrapping lets it wrap and still
+//          finds the inverse. For m up to i128::MAX nothing overflows and the three agree.
+//returns:  Returns Ok(Some(inverse)), Ok(None) if a has no inverse under Mod m (or m is 0), or the policy's error.
+#[allow(non_snake_case)]
+pub fn inverse_with_policy<P: OverflowPolicy>(a: u128, m: u128) -> Result<Option<u128>, OverflowError> {
+    if m == 0 {
+        return Ok(None);
+    }
+
+    //±1 is its own inverse. Taking it out here also keeps every quotient within i128: a quotient past i128::MAX
+    //needs B = 1 with A past i128::MAX, which only happens in the first two rows, and only for a ≅ ±1
+    let B = a % m;
+    if B == 1 || B == m - 1 {
+        return Ok(Some(B));
+    }
+
+    let mut x: i128 = 0;
+    let mut y: i128 = 1;
+    let mut A = m;
+    let mut B = B;
+
+    while B > 0 {
+        let q = A / B;
+        let r = A % B;
+        //only the last row can leave the i128 range, and its t is never read, so wrapping here is harmless
+        let t = x.wrapping_sub(P::mul(y, q as i128)?);
+
+        A = B;
+        B = r;
+        x = y;
+        y = t;
+    }
+
+    //A holds the gcd. Mod 1 there is nothing but 0, so the inverse is 0
+    if A != 1 && m != 1 {
+        return Ok(None);
+    }
+
+    //x is at most m / 2 in size, so a negative x is brought into [0, m) by adding m
+    Ok(Some(if x < 0 { m - x.unsigned_abs() } else { x as u128 }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn policies_differ_only_on_out_of_range_products() {
+        assert_eq!(Checked::mul(i128::MAX, 2), Err(OverflowError { a: i128::MAX, b: 2 }));
+        assert_eq!(Wrapping::mul(i128::MAX, 2), Ok(-2));
+        assert_eq!(Checked::mul(6, 7), Ok(42));
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows an i128")]
+    fn panicking_policy_panics_on_overflow() {
+        let _ = Panicking::mul(i128::MIN, -1);
+    }
+
+    #[test]
+    fn every_policy_agrees_up_to_i128_max() {
+        for &(a, m) in &[(3, 5), (4, 10), (2, u64::MAX as u128), (7, i128::MAX as u128), (i128::MAX as u128 - 5, i128::MAX as u128), (0, 1), (7, 0)] {
+            let expected = inverse_with_policy::<Checked>(a, m).unwrap();
+            assert_eq!(inverse_with_policy::<Panicking>(a, m).unwrap(), expected);
+            assert_eq!(inverse_with_policy::<Wrapping>(a, m).unwrap(), expected);
+        }
+        for a in 0..200u64 {
+            assert_eq!(inverse_with_policy::<Checked>(a as u128, 360), Ok(crate::inverse(a, 360).map(u128::from)));
+        }
+        assert_eq!(inverse_with_policy::<Checked>(3, 5), Ok(Some(2)));
+        assert_eq!(inverse_with_policy::<Checked>(4, 10), Ok(None));
+    }
+
+    #[test]
+    fn checked_errors_where_wrapping_wraps() {
+        //the last row multiplies -(2^127 - 1) by 2, which is past i128::MIN
+        let m = u128::MAX;
+        assert_eq!(inverse_with_policy::<Checked>(2, m), Err(OverflowError { a: -(i128::MAX), b: 2 }));
+        assert_eq!(inverse_with_policy::<Wrapping>(2, m), Ok(Some(1 << 127)));
+
+        //±1 never reaches the loop
+        assert_eq!(inverse_with_policy::<Checked>(1, m), Ok(Some(1)));
+        assert_eq!(inverse_with_policy::<Checked>(m - 1, m), Ok(Some(m - 1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows an i128")]
+    fn panicking_policy_panics_in_the_inverse() {
+        let _ = inverse_with_policy::<Panicking>(2, u128::MAX);
+    }
+}
@@ -0,0 +1,186 @@
+use std::fmt;
+use std::iter::Peekable;
+use std::str::Chars;
+
+//dev:      The ways a modulus expression can fail to parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    //The expression is empty or only contains whitespace
+    Empty,
+    //A character that doesn't belong in an expression, or a number or operator missing where one was expected
+    Unexpected { position: usize, found: Option<char> },
+    //The value doesn't fit in a u64, or a subtraction went below 0
+    OutOfRange,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "the expression is empty"),
+            ParseError::Unexpected { position, found: Some(c) } => {
+                write!(f, "unexpected '{}' at position {}", c, position)
+            }
+            ParseError::Unexpected { position, found: None } => {
+                write!(f, "the expression ends early at position {}", position)
+            }
+            ParseError::OutOfRange => write!(f, "the value doesn't fit in a u64"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+//notice:   Large moduli are usually round numbers plus or minus a little, like 1e9+7 or 2^61-1.
+//dev:      This function parses such an expression into a u64. It understands:
+//              N       a decimal number
+//              NeM     N * 10^M
+//              N^M     N to the power M
+//          combined with * and then + or -, with the usual precedence. Spaces are ignored.
+//returns:  Returns the value of the expression, or a ParseError describing the first problem.
+pub fn parse_modulus_expr(s: &str) -> Result<u64, ParseError> {
+    if s.trim().is_empty() {
+        return Err(ParseError::Empty);
+    }
+
+    let mut parser = Parser { chars: s.chars().peekable(), position: 0 };
+    let value = parser.sum()?;
+
+    match parser.peek() {
+        None => Ok(value),
+        found => Err(ParseError::Unexpected { position: parser.position, found }),
+    }
+}
+
+//dev:      A small recursive-descent parser. Every method handles one level of precedence:
+//          sum handles + and -, product handles *, and power handles e and ^.
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    position: usize,
+}
+
+impl Parser<'_> {
+    //returns:  Returns the next character that isn't whitespace, without consuming it
+    fn peek(&mut self) -> Option<char> {
+        while let Some(c) = self.chars.peek().copied() {
+            if !c.is_whitespace() {
+                return Some(c);
+            }
+            self.chars.next();
+            self.position += 1;
+        }
+        None
+    }
+
+    fn bump(&mut self) {
+        self.chars.next();
+        self.position += 1;
+    }
+
+    fn sum(&mut self) -> Result<u64, ParseError> {
+        let mut value = self.product()?;
+
+        loop {
+            match self.peek() {
+                Some('+') => {
+                    self.bump();
+                    let rhs = self.product()?;
+                    value = value.checked_add(rhs).ok_or(ParseError::OutOfRange)?;
+                }
+                Some('-') => {
+                    self.bump();
+                    let rhs = self.product()?;
+                    value = value.checked_sub(rhs).ok_or(ParseError::OutOfRange)?;
+                }
+                _ => return Ok(value),
+            }
+        }
+    }
+
+    fn product(&mut self) -> Result<u64, ParseError> {
+        let mut value = self.power()?;
+
+        while self.peek() == Some('*') {
+            self.bump();
+            let rhs = self.power()?;
+            value = value.checked_mul(rhs).ok_or(ParseError::OutOfRange)?;
+        }
+
+        Ok(value)
+    }
+
+    fn power(&mut self) -> Result<u64, ParseError> {
+        let base = self.number()?;
+
+        match self.peek() {
+            Some('e') | Some('E') => {
+                self.bump();
+                let exponent = self.exponent()?;
+                10u64
+                    .checked_pow(exponent)
+                    .and_then(|scale| base.checked_mul(scale))
+                    .ok_or(ParseError::OutOfRange)
+            }
+            Some('^') => {
+                self.bump();
+                let exponent = self.exponent()?;
+                base.checked_pow(exponent).ok_or(ParseError::OutOfRange)
+            }
+            _ => Ok(base),
+        }
+    }
+
+    fn exponent(&mut self) -> Result<u32, ParseError> {
+        let exponent = self.number()?;
+        u32::try_from(exponent).map_err(|_| ParseError::OutOfRange)
+    }
+
+    fn number(&mut self) -> Result<u64, ParseError> {
+        let found = self.peek();
+        let position = self.position;
+        let mut value: u64 = 0;
+        let mut digits = 0;
+
+        while let Some(digit) = self.chars.peek().and_then(|c| c.to_digit(10)) {
+            self.bump();
+            digits += 1;
+            value = value
+                .checked_mul(10)
+                .and_then(|v| v.checked_add(digit as u64))
+                .ok_or(ParseError::OutOfRange)?;
+        }
+
+        if digits == 0 {
+            return Err(ParseError::Unexpected { position, found });
+        }
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_common_moduli() {
+        assert_eq!(parse_modulus_expr("1e9+7"), Ok(1_000_000_007));
+        assert_eq!(parse_modulus_expr("2^31-1"), Ok(2_147_483_647));
+        assert_eq!(parse_modulus_expr(" 2^61 - 1 "), Ok((1 << 61) - 1));
+        assert_eq!(parse_modulus_expr("998244353"), Ok(998_244_353));
+        assert_eq!(parse_modulus_expr("119*2^23+1"), Ok(998_244_353));
+        assert_eq!(parse_modulus_expr("1e19"), Ok(10_000_000_000_000_000_000));
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        assert_eq!(parse_modulus_expr(""), Err(ParseError::Empty));
+        assert_eq!(parse_modulus_expr("   "), Err(ParseError::Empty));
+        assert_eq!(parse_modulus_expr("1e9+"), Err(ParseError::Unexpected { position: 4, found: None }));
+        assert_eq!(parse_modulus_expr("12x"), Err(ParseError::Unexpected { position: 2, found: Some('x') }));
+        assert_eq!(parse_modulus_expr("+7"), Err(ParseError::Unexpected { position: 0, found: Some('+') }));
+        assert_eq!(parse_modulus_expr("2^64"), Err(ParseError::OutOfRange));
+        assert_eq!(parse_modulus_expr("1e20"), Err(ParseError::OutOfRange));
+        assert_eq!(parse_modulus_expr("3-5"), Err(ParseError::OutOfRange));
+        assert_eq!(parse_modulus_expr("99999999999999999999"), Err(ParseError::OutOfRange));
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{inverse, mod_mul, mod_sub};
+
+//notice:   Long division of polynomials works just like long division of numbers: divide the leading term of what is
+//          left by the leading term of the divisor, subtract that multiple of the divisor, and repeat.
+//          Over Mod p, dividing by the divisor's leading coefficient means multiplying by its inverse, which is
+//          the same inverse every round, so it is calculated only once.
+//          Example: (x^2 + 1) / (2x + 1) Mod 5 gives quotient 3x + 1 and remainder 0, since (3x + 1)(2x + 1) = 6x^2 + 5x + 1.
+//dev:          This function divides dividend by divisor with coefficients under Mod p.
+//              Coefficients are listed from the constant term up, so [1, 0, 1] is x^2 + 1.
+//Assumption:   Assumes p is prime, so that any nonzero leading coefficient has an inverse
+//returns:      Returns Some((quotient, remainder)) with dividend = quotient * divisor + remainder and the remainder of
+//              lower degree than the divisor. Both are reduced under Mod p with trailing zeros removed, so the zero
+//              polynomial is an empty list. Returns None if the divisor is 0 or its leading coefficient has no inverse.
+pub fn poly_divmod_mod(dividend: &[u64], divisor: &[u64], p: u64) -> Option<(Vec<u64>, Vec<u64>)> {
+    if p == 0 {
+        return None;
+    }
+
+    let divisor = trimmed(divisor, p);
+    let mut remainder = trimmed(dividend, p);
+    let &leading = divisor.last()?;
+    let leading_inverse = inverse(leading, p)?;
+
+    if remainder.len() < divisor.len() {
+        return Some((Vec::new(), remainder));
+    }
+
+    let shift_count = remainder.len() - divisor.len() + 1;
+    let mut quotient = vec![0; shift_count];
+
+    //each round clears the top coefficient of the remainder
+    for shift in (0..shift_count).rev() {
+        let top = remainder[shift + divisor.len() - 1];
+        let factor = mod_mul(top, leading_inverse, p);
+        quotient[shift] = factor;
+
+        for (i, &d) in divisor.iter().enumerate() {
+            remainder[shift + i] = mod_sub(remainder[shift + i], mod_mul(factor, d, p), p);
+        }
+    }
+
+    remainder.truncate(divisor.len() - 1);
+    Some((trimmed(&quotient, p), trimmed(&remainder, p)))
+}
+
+//returns:  Returns the coefficients reduced under Mod p, without the zero coefficients at the top
+fn trimmed(coefficients: &[u64], p: u64) -> Vec<u64> {
+    let mut reduced: Vec<u64> = coefficients.iter().map(|&c| c % p).collect();
+    while reduced.last() == Some(&0) {
+        reduced.pop();
+    }
+    reduced
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mod_add;
+
+    fn multiply(a: &[u64], b: &[u64], p: u64) -> Vec<u64> {
+        let mut product = vec![0; (a.len() + b.len()).saturating_sub(1)];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                product[i + j] = mod_add(product[i + j], mod_mul(x, y, p), p);
+            }
+        }
+        product
+    }
+
+    #[test]
+    fn divides_the_example_exactly() {
+        assert_eq!(poly_divmod_mod(&[1, 0, 1], &[1, 2], 5), Some((vec![1, 3], vec![])));
+    }
+
+    #[test]
+    fn dividend_is_quotient_times_divisor_plus_remainder() {
+        let p = 13;
+        let dividend = [7, 0, 5, 11, 3, 9];
+        let divisor = [4, 1, 6];
+        let (quotient, remainder) = poly_divmod_mod(&dividend, &divisor, p).unwrap();
+        assert_eq!(quotient.len(), 4);
+        assert!(remainder.len() < divisor.len());
+
+        let mut rebuilt = multiply(&quotient, &divisor, p);
+        for (i, &r) in remainder.iter().enumerate() {
+            rebuilt[i] = mod_add(rebuilt[i], r, p);
+        }
+        assert_eq!(rebuilt, dividend);
+    }
+
+    #[test]
+    fn small_dividends_and_bad_divisors() {
+        assert_eq!(poly_divmod_mod(&[3, 4], &[1, 1, 1], 7), Some((vec![], vec![3, 4])));
+        assert_eq!(poly_divmod_mod(&[3, 4], &[0, 0], 7), None);
+        //the leading coefficient 2 has no inverse Mod 4
+        assert_eq!(poly_divmod_mod(&[1, 1, 1], &[1, 2], 4), None);
+        assert_eq!(poly_divmod_mod(&[1, 1], &[1, 1], 0), None);
+    }
+}
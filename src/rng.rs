@@ -0,0 +1,14 @@
+//dev:      A small seeded generator (SplitMix64), so the same seed always gives the same sequence.
+//          Its output is only as unpredictable as the seed, so anything secret should be seeded from
+//          a cryptographically secure source.
+pub(crate) struct SplitMix64(pub(crate) u64);
+
+impl SplitMix64 {
+    pub(crate) fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{inverse, mod_mul, mod_sub};
+
+//notice:   In a residue number system (RNS) a number x is stored as its remainders (x mod m_1, ..., x mod m_k)
+//          for pairwise relatively prime moduli m_1, ..., m_k. By the Chinese Remainder Theorem this fixes x
+//          under Mod m_1 * ... * m_k, and every component can be worked on separately.
+//          x has an inverse under the product exactly when each x mod m_i has an inverse under its own m_i,
+//          and the inverse's residues are those component inverses.
+//          Example: 2 is (2, 2) under the moduli (3, 5). The inverses are 2 Mod 3 and 3 Mod 5, i.e. (2, 3),
+//          which is 8, and 2 * 8 = 16 ≅ 1 Mod 15.
+//dev:          This function inverts a number given by its residues, one component at a time.
+//Assumption:   Assumes the moduli are pairwise relatively prime
+//returns:      Returns the residues of the inverse in the same order as moduli, or None if any component has no
+//              inverse or the two slices have different lengths.
+pub fn rns_inverse(residues: &[u64], moduli: &[u64]) -> Option<Vec<u64>> {
+    if residues.len() != moduli.len() {
+        return None;
+    }
+
+    residues.iter().zip(moduli).map(|(&r, &m)| inverse(r, m)).collect()
+}
+
+//notice:   If x is the inverse of a Mod M and y is its inverse Mod p, with M and p relatively prime, then the Chinese
+//          Remainder Theorem glues them into the inverse Mod M * p:
+//              z = x + M * ((y - x) * M^-1 mod p)
+//          which is x Mod M and y Mod p. Example: a = 2, x = 2 Mod 3 and y = 3 Mod 5 give z = 2 + 3 * (1 * 2) = 8 Mod 15.
+//dev:      The inverse of a fixed a under a modulus that keeps growing by one prime at a time. Each new prime costs
+//          two small inverses Mod p, instead of running the Euclidean loop again on the whole product.
+//          The modulus starts at 1, where the inverse is 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IncrementalCrtInverse {
+    a: u64,
+    modulus: u64,
+    inverse: Option<u64>,
+}
+
+impl IncrementalCrtInverse {
+    pub fn new(a: u64) -> Self {
+        IncrementalCrtInverse { a, modulus: 1, inverse: Some(0) }
+    }
+
+    //returns:  Returns the product of the primes added so far
+    pub fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    //dev:      Multiplies p into the modulus and updates the inverse to match. Once a has no inverse under one of the
+    //          primes it has none under the product either, and it stays None from then on.
+    //          Panics if p shares a factor with the modulus so far (e.g. the same prime twice), or if the product
+    //          doesn't fit in a u64.
+    pub fn add_prime(&mut self, p: u64) {
+        let modulus_inverse = match inverse(self.modulus, p) {
+            Some(x) => x,
+            None => panic!("{} shares a factor with the modulus {}", p, self.modulus),
+        };
+        let product = match self.modulus.checked_mul(p) {
+            Some(product) => product,
+            None => panic!("the modulus {} * {} doesn't fit in a u64", self.modulus, p),
+        };
+
+        self.inverse = self.inverse.zip(inverse(self.a, p)).map(|(x, y)| {
+            let k = mod_mul(mod_sub(y, x, p), modulus_inverse, p);
+            //x < modulus and k < p, so x + modulus * k < modulus * p, which fits
+            x + self.modulus * k
+        });
+        self.modulus = product;
+    }
+
+    //returns:  Returns the inverse of a under the product of the primes added so far, or None if there is none
+    pub fn inverse(&self) -> Option<u64> {
+        self.inverse
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //returns:  Returns the number under Mod (product of moduli) with the given residues, by the Chinese Remainder Theorem
+    fn crt(residues: &[u64], moduli: &[u64]) -> u64 {
+        residues.iter().zip(moduli).fold((0, 1), |(x, m), (&r, &p)| {
+            let k = mod_mul(mod_sub(r, x % p, p), inverse(m % p, p).unwrap(), p);
+            (x + m * k, m * p)
+        }).0
+    }
+
+    #[test]
+    fn rns_inverse_reconstructs_to_the_true_inverse() {
+        let moduli = [7u64, 11, 13, 17];
+        let product: u64 = moduli.iter().product();
+        for x in [2u64, 3, 100, 12345, product - 1] {
+            let residues: Vec<u64> = moduli.iter().map(|&m| x % m).collect();
+            let inverse_residues = rns_inverse(&residues, &moduli).unwrap();
+            assert_eq!(Some(crt(&inverse_residues, &moduli)), inverse(x, product), "x = {}", x);
+        }
+        assert_eq!(rns_inverse(&[2, 2], &[3, 5]), Some(vec![2, 3]));
+        //14 is 0 in its Mod 7 component, which has no inverse
+        assert_eq!(rns_inverse(&[0, 3], &[7, 11]), None);
+        assert_eq!(rns_inverse(&[1], &[3, 5]), None);
+    }
+
+    #[test]
+    fn incremental_inverse_matches_a_fresh_one_after_each_prime() {
+        let mut crt_inverse = IncrementalCrtInverse::new(2);
+        assert_eq!((crt_inverse.modulus(), crt_inverse.inverse()), (1, Some(0)));
+
+        for (p, modulus) in [(3, 3), (5, 15), (7, 105)] {
+            crt_inverse.add_prime(p);
+            assert_eq!(crt_inverse.modulus(), modulus);
+            assert_eq!(crt_inverse.inverse(), inverse(2, modulus));
+        }
+        assert_eq!(crt_inverse.inverse(), Some(53));
+
+        //once a prime divides a, the inverse is gone for good
+        let mut crt_inverse = IncrementalCrtInverse::new(10);
+        crt_inverse.add_prime(3);
+        crt_inverse.add_prime(5);
+        crt_inverse.add_prime(7);
+        assert_eq!((crt_inverse.modulus(), crt_inverse.inverse()), (105, None));
+    }
+
+    #[test]
+    #[should_panic(expected = "3 shares a factor with the modulus 15")]
+    fn adding_a_prime_twice_panics() {
+        let mut crt_inverse = IncrementalCrtInverse::new(2);
+        crt_inverse.add_prime(3);
+        crt_inverse.add_prime(5);
+        crt_inverse.add_prime(3);
+    }
+}
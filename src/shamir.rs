@@ -0,0 +1,99 @@
+use crate::interpolation::lagrange_interpolate;
+use crate::rng::SplitMix64;
+use crate::{mod_add, mod_mul};
+
+//notice:   Shamir's secret sharing hides a secret as the constant term of a random polynomial f of degree t - 1
+//          under Mod p, and hands out the points (x, f(x)) as shares. Any t shares pin down f, and the secret is f(0).
+//dev:          This function recovers the secret by Lagrange interpolation at x = 0. Every division in the formula
+//              is a multiplication by a modular multiplicative inverse.
+//Assumption:   Assumes p is prime and the shares come from the same polynomial
+//returns:      Returns the secret, or None if two shares have the same x Mod p.
+pub fn shamir_reconstruct(shares: &[(u64, u64)], p: u64) -> Option<u64> {
+    lagrange_interpolate(shares, 0, p)
+}
+
+//dev:          This function splits secret into count shares, any threshold of which recover it with shamir_reconstruct.
+//              It picks a polynomial f of degree threshold - 1 with f(0) = secret and seeded random coefficients,
+//              and hands out (x, f(x)) for x = 1, ..., count. The same seed always gives the same shares, so a
+//              real deployment should take the seed from a cryptographically secure source.
+//Assumption:   Assumes p is prime
+//returns:      Returns the shares. Panics unless 1 <= threshold <= count < p, since otherwise the shares
+//              either couldn't recover the secret or wouldn't all have different x values.
+pub fn shamir_split(secret: u64, threshold: usize, count: usize, p: u64, seed: u64) -> Vec<(u64, u64)> {
+    if threshold == 0 || threshold > count || count as u64 >= p {
+        panic!("shamir_split needs 1 <= threshold <= count < p, got threshold {}, count {}, p {}", threshold, count, p);
+    }
+
+    let mut rng = SplitMix64(seed);
+    //coefficients[0] is the secret, the rest are random
+    let mut coefficients = vec![secret % p];
+    coefficients.extend((1..threshold).map(|_| rng.next() % p));
+
+    (1..=count as u64)
+        .map(|x| {
+            //Horner's rule: f(x) = c0 + x(c1 + x(c2 + ...))
+            let y = coefficients.iter().rev().fold(0, |acc, &c| mod_add(mod_mul(acc, x, p), c, p));
+            (x, y)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shamir_reconstruct_from_a_subset() {
+        let p = 2_147_483_647;
+        let shares = shamir_split(123_456_789, 3, 5, p, 42);
+        assert_eq!(shamir_reconstruct(&shares[1..4], p), Some(123_456_789));
+        assert_eq!(shamir_reconstruct(&[shares[4], shares[0], shares[2]], p), Some(123_456_789));
+        assert_eq!(shamir_reconstruct(&shares, p), Some(123_456_789));
+        //two shares with the same x
+        assert_eq!(shamir_reconstruct(&[shares[0], shares[0], shares[1]], p), None);
+    }
+
+    //returns:  Returns every way of picking k items out of items, keeping their order
+    fn subsets<T: Copy>(items: &[T], k: usize) -> Vec<Vec<T>> {
+        if k == 0 {
+            return vec![Vec::new()];
+        }
+        if items.len() < k {
+            return Vec::new();
+        }
+
+        let mut with_first = subsets(&items[1..], k - 1);
+        for subset in &mut with_first {
+            subset.insert(0, items[0]);
+        }
+        with_first.extend(subsets(&items[1..], k));
+        with_first
+    }
+
+    #[test]
+    fn threshold_shares_recover_the_secret_and_fewer_do_not() {
+        let p = 1_000_000_007;
+        let secret = 31_415_926;
+        let shares = shamir_split(secret, 4, 7, p, 2024);
+        assert_eq!(shares, shamir_split(secret, 4, 7, p, 2024));
+        assert_ne!(shares, shamir_split(secret, 4, 7, p, 2025));
+
+        for subset in subsets(&shares, 4) {
+            assert_eq!(shamir_reconstruct(&subset, p), Some(secret));
+        }
+        for subset in subsets(&shares, 3) {
+            assert_ne!(shamir_reconstruct(&subset, p), Some(secret));
+        }
+    }
+
+    #[test]
+    fn threshold_one_hands_out_the_secret() {
+        assert_eq!(shamir_split(5, 1, 3, 11, 0), vec![(1, 5), (2, 5), (3, 5)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "1 <= threshold <= count < p")]
+    fn shamir_split_rejects_a_threshold_above_count() {
+        shamir_split(5, 4, 3, 11, 0);
+    }
+}
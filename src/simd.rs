@@ -0,0 +1,98 @@
+use crate::gcd;
+use std::simd::cmp::{SimdOrd, SimdPartialEq};
+use std::simd::num::SimdUint;
+use std::simd::{Select, Simd};
+
+const LANES: usize = 8;
+
+type Lanes = Simd<u32, LANES>;
+
+//notice:   The binary gcd only needs shifts, comparisons and subtractions: gcd(a, b) = 2^k * gcd(u, v) where 2^k is the
+//          largest power of 2 dividing both, and for odd u and v the pair (u, v) can be replaced by (min, max - min)
+//          with the factors of 2 shifted out again. None of the steps depend on a branch, so every SIMD lane can run
+//          the same instructions on its own pair of numbers.
+//dev:      This function calculates gcd(a[i], b[i]) for every i, LANES pairs at a time, using portable SIMD.
+//          The loop keeps going until every lane is finished; lanes that finish early are masked out of the updates.
+//          Whatever doesn't fill a whole vector at the end is handled by the scalar gcd.
+//          It is only built with the "simd" feature, which needs a nightly compiler for std::simd.
+//          Panics if a and b have different lengths.
+//returns:  Returns one gcd per pair, in order. A pair with a 0 gives the other number, and gcd(0, 0) is 0.
+pub fn gcd_batch_u32(a: &[u32], b: &[u32]) -> Vec<u32> {
+    assert_eq!(a.len(), b.len(), "gcd_batch_u32 needs two slices of the same length");
+
+    let mut gcds = Vec::with_capacity(a.len());
+    let (a_chunks, a_rest) = a.as_chunks::<LANES>();
+    let (b_chunks, b_rest) = b.as_chunks::<LANES>();
+
+    for (a_chunk, b_chunk) in a_chunks.iter().zip(b_chunks) {
+        let gcd_lanes = gcd_lanes(Lanes::from_array(*a_chunk), Lanes::from_array(*b_chunk));
+        gcds.extend_from_slice(gcd_lanes.as_array());
+    }
+
+    gcds.extend(a_rest.iter().zip(b_rest).map(|(&x, &y)| gcd(x as u64, y as u64) as u32));
+    gcds
+}
+
+//dev:      The binary gcd of LANES pairs at once.
+fn gcd_lanes(a: Lanes, b: Lanes) -> Lanes {
+    let zero = Lanes::splat(0);
+    let one = Lanes::splat(1);
+    let has_zero = a.simd_eq(zero) | b.simd_eq(zero);
+
+    //lanes with a 0 are answered at the end, here they are given 1s so the loop still finishes
+    let mut u = has_zero.select(one, a);
+    let mut v = has_zero.select(one, b);
+    let shift = (u | v).trailing_zeros();
+    u >>= u.trailing_zeros();
+
+    loop {
+        let active = v.simd_ne(zero);
+        if !active.any() {
+            break;
+        }
+
+        //finished lanes (v = 0) must keep their u and v, and shift by 0 because the trailing zeros of 0 would be
+        //a shift by the full width
+        v >>= active.select(v.trailing_zeros(), zero);
+        let (low, high) = (u.simd_min(v), u.simd_max(v));
+        u = active.select(low, u);
+        v = active.select(high - low, zero);
+    }
+
+    has_zero.select(a | b, u << shift)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+
+    #[test]
+    fn matches_the_scalar_gcd_on_random_arrays() {
+        let mut rng = SplitMix64(273);
+        for len in [0, 1, 7, 8, 9, 64, 101] {
+            for round in 0..20 {
+                //small values every other round, so the gcds aren't almost all 1
+                let limit = if round % 2 == 0 { u32::MAX as u64 + 1 } else { 1000 };
+                let a: Vec<u32> = (0..len).map(|_| (rng.next() % limit) as u32).collect();
+                let b: Vec<u32> = (0..len).map(|_| (rng.next() % limit) as u32).collect();
+
+                let expected: Vec<u32> = a.iter().zip(&b).map(|(&x, &y)| gcd(x as u64, y as u64) as u32).collect();
+                assert_eq!(gcd_batch_u32(&a, &b), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn zeros_and_powers_of_two() {
+        let a = [0, 0, 12, 1 << 31, 48, u32::MAX, 1, 96];
+        let b = [0, 5, 0, 1 << 20, 18, u32::MAX, u32::MAX, 64];
+        assert_eq!(gcd_batch_u32(&a, &b), vec![0, 5, 12, 1 << 20, 6, u32::MAX, 1, 32]);
+    }
+
+    #[test]
+    #[should_panic(expected = "gcd_batch_u32 needs two slices of the same length")]
+    fn different_lengths_panic() {
+        gcd_batch_u32(&[1, 2], &[1]);
+    }
+}
@@ -0,0 +1,365 @@
+use std::fmt;
+
+//dev:      One row of the Extended Euclidean table from the documentation: | Q | A | B | R | x | y | T |
+//          q = A / B, r = A % B and t = x - y * q. The next row starts from A = B, B = r, x = y, y = t.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub q: u64,
+    pub a: u64,
+    pub b: u64,
+    pub r: u64,
+    pub x: i128,
+    pub y: i128,
+    pub t: i128,
+}
+
+//dev:      This function runs the same loop as modular_multiplicative_inverse, but records every row of the table.
+//          x, y and t are i128 so the recorded values never overflow.
+//returns:  Returns the inverse of a under Mod b (None if it doesn't exist or b is 0), together with the rows.
+pub fn inverse_with_steps(a: u64, b: u64) -> (Option<u64>, Vec<Step>) {
+    let mut steps = Vec::new();
+    let inverse = inverse_with_steps_into(a, b, &mut steps);
+    (inverse, steps)
+}
+
+//dev:      This function is inverse_with_steps for hot loops: the rows go into a buffer owned by the caller,
+//          which is cleared first, so calling it again and again reuses the same allocation.
+//returns:  Returns the inverse of a under Mod b, or None if it doesn't exist or b is 0.
+#[allow(non_snake_case)]
+pub fn inverse_with_steps_into(a: u64, b: u64, steps: &mut Vec<Step>) -> Option<u64> {
+    steps.clear();
+
+    if b == 0 {
+        return None;
+    }
+
+    let mut x: i128 = 0;
+    let mut y: i128 = 1;
+    let mut A = b;
+    let mut B = a;
+
+    while B > 0 {
+        let q = A / B;
+        let r = A % B;
+        let t = x - y * q as i128;
+
+        steps.push(Step { q, a: A, b: B, r, x, y, t });
+
+        A = B;
+        B = r;
+        x = y;
+        y = t;
+    }
+
+    //A holds the gcd once B reaches 0
+    if A != 1 {
+        return None;
+    }
+
+    Some(x.rem_euclid(b as i128) as u64)
+}
+
+//notice:   The coefficients in the table never grow past the modulus: for a < m they stay at most m in absolute value,
+//          and the very last T is always ±m / GCD(a, m). That bound is why i128 coefficients are safe for any u64 modulus.
+//dev:      This function runs the loop through inverse_with_steps and reports the largest |x|, |y| or |T| in any row,
+//          which shows how large the coefficients really get.
+//returns:  Returns the inverse of a under Mod m (None if it doesn't exist or m is 0) and the largest absolute
+//          coefficient, capped at i64::MAX. With no rows (a = 0 or m = 0) the largest coefficient is 0.
+pub fn inverse_with_max_coeff(a: u64, m: u64) -> (Option<u64>, i64) {
+    let (inverse, steps) = inverse_with_steps(a, m);
+
+    let max_coefficient = steps
+        .iter()
+        .flat_map(|step| [step.x, step.y, step.t])
+        .map(i128::unsigned_abs)
+        .max()
+        .unwrap_or(0);
+
+    (inverse, i64::try_from(max_coefficient).unwrap_or(i64::MAX))
+}
+
+//dev:      The two rows of the Blankinship matrix. Each row [r, x, y] keeps the invariant r = a * x + m * y.
+pub type BlankinshipMatrix = [[i128; 3]; 2];
+
+//notice:   Blankinship's method is the Extended Euclidean Algorithm written as row reduction. Start from the matrix
+//              | a  1  0 |
+//              | m  0  1 |
+//          and keep subtracting q times the row with the smaller first entry from the other row, with q the quotient
+//          of the first entries. Every row always satisfies r = a * x + m * y, and the first column goes through the
+//          same remainders as the table in the documentation. When one first entry reaches 0, the other row is
+//          [GCD(a, m), x, y], i.e. Bézout's identity.
+//dev:      This function records the matrix before the first reduction and after every one.
+//returns:  Returns the matrices in order. The last one has a 0 in the first column of one of its rows.
+pub fn blankinship_steps(a: u64, m: u64) -> Vec<BlankinshipMatrix> {
+    let mut matrix: BlankinshipMatrix = [[a as i128, 1, 0], [m as i128, 0, 1]];
+    let mut steps = vec![matrix];
+
+    while matrix[0][0] != 0 && matrix[1][0] != 0 {
+        let (big, small) = if matrix[0][0] >= matrix[1][0] { (0, 1) } else { (1, 0) };
+        let q = matrix[big][0] / matrix[small][0];
+
+        let small_row = matrix[small];
+        for (entry, small_entry) in matrix[big].iter_mut().zip(small_row) {
+            *entry -= q * small_entry;
+        }
+        steps.push(matrix);
+    }
+
+    steps
+}
+
+//dev:      This function calculates the inverse with Blankinship's method. x from the row holding the gcd
+//          is the inverse of a, once brought into [0, m).
+//returns:  Returns the inverse of a under Mod m, or None if it doesn't exist or m is 0.
+pub fn inverse_blankinship(a: u64, m: u64) -> Option<u64> {
+    if m == 0 {
+        return None;
+    }
+
+    let last = *blankinship_steps(a, m).last()?;
+    let [gcd, x, _] = if last[0][0] != 0 { last[0] } else { last[1] };
+
+    if gcd != 1 {
+        return None;
+    }
+
+    Some(x.rem_euclid(m as i128) as u64)
+}
+
+//dev:      This function lays the rows out like the table in the documentation, including the last row
+//          where B has reached 0 and only A, x and y are left.
+//returns:  Returns the table with one line per row, each ending with a newline. An empty list gives an empty string.
+pub fn format_steps(steps: &[Step]) -> String {
+    let last = match steps.last() {
+        Some(last) => last,
+        None => return String::new(),
+    };
+
+    let mut rows: Vec<[String; 7]> = vec![["Q", "A", "B", "R", "x", "y", "T"].map(String::from)];
+    for step in steps {
+        rows.push([
+            step.q.to_string(),
+            step.a.to_string(),
+            step.b.to_string(),
+            step.r.to_string(),
+            step.x.to_string(),
+            step.y.to_string(),
+            step.t.to_string(),
+        ]);
+    }
+    //the shifting after the last row leaves A = B, B = r = 0, x = y and y = t
+    rows.push([
+        "-".to_string(),
+        last.b.to_string(),
+        last.r.to_string(),
+        "-".to_string(),
+        last.y.to_string(),
+        last.t.to_string(),
+        "-".to_string(),
+    ]);
+
+    let mut widths = [0; 7];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let mut table = String::new();
+    for (index, row) in rows.iter().enumerate() {
+        for (cell, width) in row.iter().zip(widths) {
+            table.push_str(&format!("| {:>width$} ", cell, width = width));
+        }
+        table.push_str("|\n");
+
+        //a separator line under the header
+        if index == 0 {
+            for width in widths {
+                table.push_str(&format!("|{}", "-".repeat(width + 2)));
+            }
+            table.push_str("|\n");
+        }
+    }
+
+    table
+}
+
+//dev:      Wrapping a pair in InverseDebug(a, b) and printing it with {:?} or dbg! shows the whole
+//          Extended Euclidean table for the inverse of a under Mod b, followed by the answer.
+pub struct InverseDebug(pub u64, pub u64);
+
+impl fmt::Debug for InverseDebug {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (inverse, steps) = inverse_with_steps(self.0, self.1);
+
+        writeln!(f, "InverseDebug({}, {})", self.0, self.1)?;
+        write!(f, "{}", format_steps(&steps))?;
+
+        match inverse {
+            Some(x) => write!(f, "The modular multiplicative inverse of {} Mod {} is {}", self.0, self.1, x),
+            None => write!(f, "{} has no modular multiplicative inverse Mod {}", self.0, self.1),
+        }
+    }
+}
+
+//dev:      This function writes out the whole calculation of the inverse of a under Mod m as a proof:
+//          the table of the Extended Euclidean Algorithm, reading x off the last row, bringing x into [0, m),
+//          and checking that a times the answer really leaves a remainder of 1.
+//returns:  Returns the proof, or None if a has no inverse under Mod m.
+pub fn inverse_proof(a: u64, m: u64) -> Option<String> {
+    let (inverse, steps) = inverse_with_steps(a, m);
+    let inverse = inverse?;
+
+    //after the last row is shifted, x takes the value of y. With no rows at all x keeps its starting value 0
+    let raw_x = steps.last().map_or(0, |step| step.y);
+    let gcd = steps.last().map_or(m, |step| step.b);
+
+    let mut proof = format!("Step 1: run the Extended Euclidean Algorithm on A = {} and B = {}.\n", m, a);
+    proof.push_str(&format_steps(&steps));
+
+    proof.push_str(&format!(
+        "Step 2: B has reached 0 with A = {}, so GCD({}, {}) = {} and the inverse exists. The last value of x is {}.\n",
+        gcd, a, m, gcd, raw_x
+    ));
+
+    if raw_x < 0 {
+        proof.push_str(&format!(
+            "Step 3: x = {} is negative, so we add {}: {} + {} = {}.\n",
+            raw_x, m, raw_x, m, inverse
+        ));
+    } else {
+        proof.push_str(&format!(
+            "Step 3: x = {} is already between 0 and {}, so it is the inverse.\n",
+            inverse,
+            m - 1
+        ));
+    }
+
+    let product = a as u128 * inverse as u128;
+    proof.push_str(&format!(
+        "Step 4: {} * {} = {} = {} * {} + {}, so {} * {} ≡ 1 (mod {}).\n",
+        a,
+        inverse,
+        product,
+        product / m as u128,
+        m,
+        product % m as u128,
+        a,
+        inverse,
+        m
+    ));
+
+    Some(proof)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::SplitMix64;
+
+    #[test]
+    fn inverse_with_steps_records_every_row() {
+        let (inverse, steps) = inverse_with_steps(3, 7);
+        assert_eq!(inverse, Some(5));
+        assert_eq!(
+            steps,
+            vec![
+                Step { q: 2, a: 7, b: 3, r: 1, x: 0, y: 1, t: -2 },
+                Step { q: 3, a: 3, b: 1, r: 0, x: 1, y: -2, t: 7 },
+            ]
+        );
+        assert_eq!(inverse_with_steps(4, 10).0, None);
+        assert_eq!(inverse_with_steps(4, 0), (None, Vec::new()));
+    }
+
+    #[test]
+    fn inverse_debug_shows_the_table_and_the_answer() {
+        let output = format!("{:?}", InverseDebug(3, 7));
+        assert!(output.starts_with("InverseDebug(3, 7)\n"));
+        assert!(output.contains("| Q | A | B | R |  x |  y |  T |"));
+        assert!(output.contains("| 2 | 7 | 3 | 1 |  0 |  1 | -2 |"));
+        assert!(output.contains("| 3 | 3 | 1 | 0 |  1 | -2 |  7 |"));
+        assert!(output.ends_with("The modular multiplicative inverse of 3 Mod 7 is 5"));
+
+        assert!(format!("{:?}", InverseDebug(4, 10)).ends_with("4 has no modular multiplicative inverse Mod 10"));
+        assert_eq!(format_steps(&[]), "");
+    }
+
+    #[test]
+    fn inverse_with_steps_into_resets_the_buffer() {
+        let mut buffer = Vec::new();
+        assert_eq!(inverse_with_steps_into(34, 55, &mut buffer), Some(34));
+        assert_eq!(buffer.len(), 8);
+        let capacity = buffer.capacity();
+
+        assert_eq!(inverse_with_steps_into(3, 7, &mut buffer), Some(5));
+        assert_eq!(buffer, inverse_with_steps(3, 7).1);
+        assert_eq!(buffer.capacity(), capacity);
+
+        assert_eq!(inverse_with_steps_into(3, 0, &mut buffer), None);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn inverse_proof_has_every_stage() {
+        let proof = inverse_proof(3, 5).unwrap();
+        assert!(proof.starts_with("Step 1: run the Extended Euclidean Algorithm on A = 5 and B = 3.\n"));
+        assert!(proof.contains("| 2 | 2 | 1 | 0 | -1 |  2 | -5 |"));
+        assert!(proof.contains("Step 2: B has reached 0 with A = 1, so GCD(3, 5) = 1 and the inverse exists. The last value of x is 2.\n"));
+        assert!(proof.contains("Step 3: x = 2 is already between 0 and 4, so it is the inverse.\n"));
+        assert!(proof.ends_with("Step 4: 3 * 2 = 6 = 1 * 5 + 1, so 3 * 2 ≡ 1 (mod 5).\n"));
+
+        assert!(inverse_proof(3, 7).unwrap().contains("Step 3: x = -2 is negative, so we add 7: -2 + 7 = 5.\n"));
+        assert_eq!(inverse_proof(4, 10), None);
+    }
+
+    #[test]
+    fn max_coefficient_is_bounded_by_the_modulus() {
+        assert_eq!(inverse_with_max_coeff(3, 7).0, Some(5));
+        assert_eq!(inverse_with_max_coeff(0, 7), (None, 0));
+        assert_eq!(inverse_with_max_coeff(3, 0), (None, 0));
+
+        let mut rng = SplitMix64(286);
+        for _ in 0..200 {
+            let m = rng.next() % (1 << 62) + 2;
+            let a = rng.next() % m;
+            let (x, max_coefficient) = inverse_with_max_coeff(a, m);
+            assert_eq!(x, crate::inverse(a, m));
+            if x.is_some() {
+                assert!(max_coefficient as u64 <= m, "a = {}, m = {}: {}", a, m, max_coefficient);
+            }
+        }
+
+        //consecutive Fibonacci numbers take the most rows, and still stay within the bound
+        let m = 7_540_113_804_746_346_429;
+        let (x, max_coefficient) = inverse_with_max_coeff(4_660_046_610_375_530_309, m);
+        assert!(x.is_some());
+        assert!(max_coefficient as u64 <= m);
+    }
+
+    #[test]
+    fn blankinship_matrices_for_3_mod_7() {
+        assert_eq!(blankinship_steps(3, 7), vec![
+            [[3, 1, 0], [7, 0, 1]],
+            [[3, 1, 0], [1, -2, 1]],
+            [[0, 7, -3], [1, -2, 1]]
+        ]);
+        assert_eq!(inverse_blankinship(3, 7), Some(5));
+    }
+
+    #[test]
+    fn blankinship_agrees_with_the_standard_inverse() {
+        for m in [0, 1, 2, 10, 97, 360, 1_000_000_007] {
+            for a in [0, 1, 2, 3, 7, 96, 359, 1_000_000_006] {
+                assert_eq!(inverse_blankinship(a, m), crate::inverse(a, m), "a = {}, m = {}", a, m);
+
+                //every row keeps r = a * x + m * y
+                for matrix in blankinship_steps(a, m) {
+                    for [r, x, y] in matrix {
+                        assert_eq!(r, a as i128 * x + m as i128 * y);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use crate::inverse;
+use std::collections::VecDeque;
+
+//dev:      Keeps the inverses of the last window_size values pushed into it, all under the same modulus.
+//          Each inverse is calculated once, when its value is pushed, and dropped when the value leaves the window.
+#[derive(Debug, Clone)]
+pub struct WindowedInverse {
+    modulus: u64,
+    window_size: usize,
+    window: VecDeque<Option<u64>>,
+}
+
+impl WindowedInverse {
+    pub fn new(modulus: u64, window_size: usize) -> Self {
+        WindowedInverse { modulus, window_size, window: VecDeque::with_capacity(window_size) }
+    }
+
+    //dev:      Adds a to the window, pushing out the oldest value once the window is full.
+    pub fn push(&mut self, a: u64) {
+        if self.window_size == 0 {
+            return;
+        }
+
+        if self.window.len() == self.window_size {
+            self.window.pop_front();
+        }
+        self.window.push_back(inverse(a, self.modulus));
+    }
+
+    //returns:  Returns the inverses of the values in the window, oldest first. None marks a value with no inverse.
+    pub fn current_inverses(&self) -> Vec<Option<u64>> {
+        self.window.iter().copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_matches_fresh_inverses() {
+        let values = [3, 4, 5, 6, 7, 8, 9];
+        let mut window = WindowedInverse::new(10, 3);
+        for (i, &a) in values.iter().enumerate() {
+            window.push(a);
+            let start = (i + 1).saturating_sub(3);
+            let expected: Vec<Option<u64>> = values[start..=i].iter().map(|&a| inverse(a, 10)).collect();
+            assert_eq!(window.current_inverses(), expected);
+        }
+        assert_eq!(window.current_inverses(), vec![Some(3), None, Some(9)]);
+
+        let mut empty = WindowedInverse::new(10, 0);
+        empty.push(3);
+        assert!(empty.current_inverses().is_empty());
+    }
+}
@@ -0,0 +1,479 @@
+use crate::number_theory::factorize;
+use crate::{gcd, inverse, mod_mul};
+use std::io::{self, Write};
+use std::sync::Arc;
+
+//dev:      This function calculates the inverse of every residue under Mod m.
+//returns:  Returns a list where entry a is Some(inverse of a), or None if a has no inverse.
+pub fn inverse_table(m: u64) -> Vec<Option<u64>> {
+    (0..m).map(|a| inverse(a, m)).collect()
+}
+
+//dev:      This function lays out inverse_table(m) as two aligned columns, one "a -> inverse" line per residue,
+//          so it can be printed straight to a terminal. Residues without an inverse show "—".
+//returns:  Returns the formatted table. Every line, including the last one, ends with a newline.
+pub fn format_inverse_table(m: u64) -> String {
+    //the left column is as wide as the biggest residue, m - 1
+    let width = m.saturating_sub(1).to_string().len();
+    let mut table = String::new();
+
+    for (a, entry) in inverse_table(m).into_iter().enumerate() {
+        let right = match entry {
+            Some(x) => x.to_string(),
+            None => String::from("—"),
+        };
+        table.push_str(&format!("{:>width$} -> {}\n", a, right, width = width));
+    }
+
+    table
+}
+
+//dev:      This function formats the inverse with leading zeros up to width digits, which keeps columns
+//          aligned in CSV or TSV output. A number with more digits than width is left as it is.
+//returns:  Returns the zero-padded inverse, or None if a has no inverse under Mod m.
+pub fn inverse_padded(a: u64, m: u64, width: usize) -> Option<String> {
+    inverse(a, m).map(|x| format!("{:0width$}", x, width = width))
+}
+
+//dev:      This function writes inverse_table(m) as CSV, so it can be opened in a spreadsheet.
+//          The header is "a,inverse" and every residue gets a row. The inverse cell is left empty when there is none.
+//returns:  Returns any error from the writer.
+pub fn write_inverse_table_csv<W: Write>(m: u64, writer: &mut W) -> io::Result<()> {
+    writeln!(writer, "a,inverse")?;
+
+    for (a, entry) in inverse_table(m).into_iter().enumerate() {
+        match entry {
+            Some(x) => writeln!(writer, "{},{}", a, x)?,
+            None => writeln!(writer, "{},", a)?,
+        }
+    }
+
+    Ok(())
+}
+
+//notice:   The multiplication table (Cayley table) of Mod m shows the whole structure at a glance: a row with a 1 in it
+//          belongs to a unit, the column of that 1 is its inverse, and the rows without a 1 are the non-units.
+//dev:      This function builds the full m x m table, with entry [i][j] = (i * j) mod m. It holds m^2 numbers,
+//          so it is only meant for small moduli.
+//returns:  Returns the table as a list of rows. 0 gives an empty table.
+pub fn multiplication_table(m: u64) -> Vec<Vec<u64>> {
+    (0..m).map(|i| (0..m).map(|j| mod_mul(i, j, m)).collect()).collect()
+}
+
+//dev:      This function reads the inverse pairs off multiplication_table(m): the positions (i, j) holding 1 mod m.
+//          Every unit has exactly one such position in its row, so the result is the same as inverse_pairs(m).
+//returns:  Returns the (i, j) positions in row order.
+pub fn inverse_positions(m: u64) -> Vec<(u64, u64)> {
+    let one = 1 % m.max(1);
+
+    let mut positions = Vec::new();
+
+    for (i, row) in (0..).zip(multiplication_table(m)) {
+        for (j, product) in (0..).zip(row) {
+            if product == one {
+                positions.push((i, j));
+            }
+        }
+    }
+
+    positions
+}
+
+//dev:      One row of results for a data pipeline: the inputs, the inverse if there is one, and the gcd that decides it.
+//          to_csv_record turns it into a line matching InverseRow::CSV_HEADER, and with the serde feature
+//          it can go through any serde format as well.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InverseRow {
+    pub a: u64,
+    pub modulus: u64,
+    pub inverse: Option<u64>,
+    pub gcd: u64,
+}
+
+impl InverseRow {
+    pub const CSV_HEADER: &'static str = "a,modulus,inverse,gcd";
+
+    //dev:      Calculates the row for a under Mod m.
+    pub fn compute(a: u64, m: u64) -> Self {
+        InverseRow { a, modulus: m, inverse: inverse(a, m), gcd: gcd(a, m) }
+    }
+
+    //returns:  Returns the row as comma separated values, with the inverse cell left empty when there is none
+    pub fn to_csv_record(&self) -> String {
+        match self.inverse {
+            Some(x) => format!("{},{},{},{}", self.a, self.modulus, x, self.gcd),
+            None => format!("{},{},,{}", self.a, self.modulus, self.gcd),
+        }
+    }
+}
+
+//notice:   For most moduli the residues without an inverse are the minority: they are the multiples of the primes
+//          dividing m, m - φ(m) of them in total. Example: Mod 10 only 0, 2, 4, 5, 6 and 8 have no inverse.
+//dev:      This function lists just those residues instead of a full table. They are generated directly as the
+//          multiples of each prime factor of m, so no gcd is calculated for the residues that are units.
+//returns:  Returns the residues in [0, m) with no inverse, sorted, the same list as non_units_mod(m).
+pub fn sparse_non_invertible(m: u64) -> Vec<u64> {
+    let mut non_units: Vec<u64> = factorize(m).into_iter().flat_map(|(p, _)| (0..m).step_by(p as usize)).collect();
+
+    //a multiple of two different primes is generated once for each of them
+    non_units.sort_unstable();
+    non_units.dedup();
+    non_units
+}
+
+//dev:          This function checks whether a has an inverse using the list from sparse_non_invertible,
+//              with a binary search instead of a gcd.
+//Assumption:   Assumes a is already reduced, i.e. a < m for the m the list was made from
+//returns:      Returns true if a isn't in the list, i.e. it has an inverse.
+pub fn is_invertible_via_sparse(a: u64, sparse: &[u64]) -> bool {
+    sparse.binary_search(&a).is_err()
+}
+
+//dev:      A small modulus with the inverse of every residue worked out up front, in new.
+//          After that every inverse is a lookup. The table takes m entries, so this is meant for small moduli.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixedModulus {
+    modulus: u64,
+    table: Vec<Option<u64>>,
+}
+
+impl FixedModulus {
+    pub fn new(m: u64) -> Self {
+        FixedModulus { modulus: m, table: inverse_table(m) }
+    }
+
+    pub fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    //returns:  Returns the inverse of a under the fixed modulus, or None if it doesn't exist (or the modulus is 0)
+    pub fn inverse(&self, a: u64) -> Option<u64> {
+        if self.modulus == 0 {
+            return None;
+        }
+
+        self.table[(a % self.modulus) as usize]
+    }
+}
+
+//dev:      The entry point for inverse tables that are meant to be shared between threads, see snapshot.
+pub struct InverseTable;
+
+impl InverseTable {
+    //dev:      Works out the inverse of every residue Mod m up front and wraps the result in an Arc.
+    //          The snapshot never changes after this, so any number of threads can read it at once without a lock;
+    //          cloning the Arc is the only cost of handing it to another thread.
+    pub fn snapshot(m: u64) -> Arc<InverseTableSnapshot> {
+        Arc::new(InverseTableSnapshot { modulus: m, table: inverse_table(m).into_boxed_slice() })
+    }
+}
+
+//dev:      An immutable inverse table for one modulus, made by InverseTable::snapshot. It has no methods that take
+//          &mut self and nothing inside it can change behind a &self (no Cell, Mutex or atomics), so it is Sync
+//          and a read is a plain index into the table.
+#[derive(Debug, PartialEq, Eq)]
+pub struct InverseTableSnapshot {
+    modulus: u64,
+    table: Box<[Option<u64>]>,
+}
+
+impl InverseTableSnapshot {
+    pub fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    //returns:  Returns the inverse of a under the snapshot's modulus, or None if it doesn't exist (or the modulus is 0)
+    pub fn get(&self, a: u64) -> Option<u64> {
+        if self.modulus == 0 {
+            return None;
+        }
+
+        self.table[(a % self.modulus) as usize]
+    }
+}
+
+//dev:      A version of the inverse that can run while compiling, for building tables in constants.
+//          It is the same Extended Euclidean loop, written with only the operations a const fn is allowed to use.
+//returns:  Returns Some(inverse) of a under Mod m, or None if it doesn't exist or m is 0.
+pub const fn const_inverse(a: u64, m: u64) -> Option<u64> {
+    if m == 0 {
+        return None;
+    }
+
+    let (mut big, mut small) = (m, a % m);
+    let (mut x, mut y): (i128, i128) = (0, 1);
+
+    while small > 0 {
+        let q = big / small;
+        (big, small) = (small, big % small);
+        (x, y) = (y, x - y * q as i128);
+    }
+
+    //big holds the gcd. Mod 1 the only residue is 0, and x is still 0
+    if big != 1 && m != 1 {
+        return None;
+    }
+
+    Some(x.rem_euclid(m as i128) as u64)
+}
+
+//dev:      The modulus baked into the build. It is read from the INVERSE_MODULUS environment variable while compiling
+//          (e.g. INVERSE_MODULUS=998244353 cargo build), and is 1e9+7 when that isn't set.
+//          A value that isn't a decimal number greater than 0 stops the build.
+pub const MODULUS: u64 = match option_env!("INVERSE_MODULUS") {
+    Some(value) => parse_modulus(value),
+    None => 1_000_000_007,
+};
+
+//dev:      Reads the decimal number in INVERSE_MODULUS. Any problem panics, which in a const is a compile error.
+const fn parse_modulus(value: &str) -> u64 {
+    let digits = value.as_bytes();
+    assert!(!digits.is_empty(), "INVERSE_MODULUS is empty");
+
+    let mut modulus: u64 = 0;
+    let mut i = 0;
+    while i < digits.len() {
+        assert!(digits[i].is_ascii_digit(), "INVERSE_MODULUS must be a decimal number");
+        modulus = match modulus.checked_mul(10) {
+            Some(shifted) => match shifted.checked_add((digits[i] - b'0') as u64) {
+                Some(next) => next,
+                None => panic!("INVERSE_MODULUS doesn't fit in a u64"),
+            },
+            None => panic!("INVERSE_MODULUS doesn't fit in a u64"),
+        };
+        i += 1;
+    }
+
+    assert!(modulus > 0, "INVERSE_MODULUS must be greater than 0");
+    modulus
+}
+
+//dev:      This function calculates the inverse of a under the built-in MODULUS. With the modulus a constant,
+//          the compiler turns the first reduction a % MODULUS into a multiplication, and when a is a constant as well
+//          the whole inverse is worked out while compiling.
+//returns:  Returns Some(inverse) of a under Mod MODULUS, or None if it doesn't exist.
+#[inline]
+pub const fn inverse_fixed(a: u64) -> Option<u64> {
+    const_inverse(a, MODULUS)
+}
+
+//dev:      Builds the inverse table for the modulus N, one entry per residue, at compile time when used in a const.
+pub const fn const_inverse_table<const N: usize>() -> [Option<u64>; N] {
+    let mut table = [None; N];
+    let mut a = 0;
+
+    while a < N {
+        table[a] = const_inverse(a as u64, N as u64);
+        a += 1;
+    }
+
+    table
+}
+
+//dev:      inverse_table!(7) expands to a [Option<u64>; 7] array of inverses that is worked out while compiling,
+//          so embedding it costs nothing at runtime. The modulus has to be a literal.
+#[macro_export]
+macro_rules! inverse_table {
+    ($modulus:literal) => {{
+        const TABLE: [Option<u64>; $modulus] = $crate::table::const_inverse_table::<$modulus>();
+        TABLE
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inverse_row_fields_and_csv() {
+        let row = InverseRow::compute(3, 7);
+        assert_eq!(row, InverseRow { a: 3, modulus: 7, inverse: Some(5), gcd: 1 });
+        assert_eq!(row.to_csv_record(), "3,7,5,1");
+
+        let row = InverseRow::compute(4, 10);
+        assert_eq!(row, InverseRow { a: 4, modulus: 10, inverse: None, gcd: 2 });
+        assert_eq!(row.to_csv_record(), "4,10,,2");
+
+        assert_eq!(InverseRow::compute(0, 1).to_csv_record(), "0,1,0,1");
+        assert_eq!(InverseRow::CSV_HEADER.split(',').count(), row.to_csv_record().split(',').count());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn inverse_row_serializes_with_serde() {
+        let rows: Vec<InverseRow> = [(3, 7), (4, 10), (0, 1)].iter().map(|&(a, m)| InverseRow::compute(a, m)).collect();
+        let json = serde_json::to_string(&rows).unwrap();
+        assert_eq!(
+            json,
+            r#"[{"a":3,"modulus":7,"inverse":5,"gcd":1},{"a":4,"modulus":10,"inverse":null,"gcd":2},{"a":0,"modulus":1,"inverse":0,"gcd":1}]"#
+        );
+        assert_eq!(serde_json::from_str::<Vec<InverseRow>>(&json).unwrap(), rows);
+    }
+
+    #[test]
+    fn snapshot_is_shared_between_threads() {
+        //no lock is needed to read a snapshot from several threads: it is Send + Sync, and it has no interior
+        //mutability, so the compiler itself rules out a write racing a read
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<InverseTableSnapshot>();
+
+        let snapshot = InverseTable::snapshot(10);
+        assert_eq!(snapshot.modulus(), 10);
+
+        let handles: Vec<_> = (0..4u64)
+            .map(|t| {
+                let snapshot = Arc::clone(&snapshot);
+                std::thread::spawn(move || (t..10).step_by(4).map(|a| snapshot.get(a)).collect::<Vec<_>>())
+            })
+            .collect();
+        let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert_eq!(results[1], vec![Some(1), None, Some(9)]);
+        assert_eq!(results[3], vec![Some(7), Some(3)]);
+        for a in 0..25 {
+            assert_eq!(snapshot.get(a), inverse(a, 10));
+        }
+        assert_eq!(InverseTable::snapshot(0).get(5), None);
+    }
+
+    #[test]
+    fn inverse_table_for_five() {
+        assert_eq!(inverse_table(5), vec![None, Some(1), Some(3), Some(2), Some(4)]);
+        assert_eq!(format_inverse_table(5), "0 -> —\n1 -> 1\n2 -> 3\n3 -> 2\n4 -> 4\n");
+        assert!(inverse_table(0).is_empty());
+        assert_eq!(format_inverse_table(0), "");
+    }
+
+    #[test]
+    fn format_inverse_table_aligns_the_left_column() {
+        let table = format_inverse_table(11);
+        assert!(table.starts_with(" 0 -> —\n 1 -> 1\n"));
+        assert!(table.ends_with("10 -> 10\n"));
+        assert_eq!(table.lines().count(), 11);
+    }
+
+    #[test]
+    fn inverse_padded_adds_leading_zeros() {
+        assert_eq!(inverse_padded(3, 5, 4), Some("0002".to_string()));
+        assert_eq!(inverse_padded(3, 1_000_000_007, 4), Some("333333336".to_string()));
+        assert_eq!(inverse_padded(3, 7, 0), Some("5".to_string()));
+        assert_eq!(inverse_padded(4, 10, 4), None);
+    }
+
+    #[test]
+    fn inverse_table_csv_round_trips() {
+        let mut buffer: Vec<u8> = Vec::new();
+        write_inverse_table_csv(10, &mut buffer).unwrap();
+        let csv = String::from_utf8(buffer).unwrap();
+
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("a,inverse"));
+        let rows: Vec<(u64, Option<u64>)> = lines
+            .map(|line| {
+                let (a, x) = line.split_once(',').unwrap();
+                (a.parse().unwrap(), x.parse().ok())
+            })
+            .collect();
+
+        assert_eq!(rows.len(), 10);
+        for (a, x) in rows {
+            assert_eq!(x, inverse(a, 10), "a = {}", a);
+        }
+        assert!(csv.contains("\n3,7\n") && csv.contains("\n4,\n"));
+    }
+
+    #[test]
+    fn fixed_modulus_lookups_match_fresh_inverses() {
+        let fixed = FixedModulus::new(7);
+        assert_eq!(fixed.modulus(), 7);
+        for a in 0..50 {
+            assert_eq!(fixed.inverse(a), inverse(a, 7));
+        }
+        //the table is built once in new and only read afterwards, so a clone holds the very same entries
+        assert_eq!(fixed.clone(), fixed);
+        assert_eq!(fixed.table.len(), 7);
+        assert_eq!(FixedModulus::new(0).inverse(3), None);
+    }
+
+    #[test]
+    fn inverse_table_macro_is_built_at_compile_time() {
+        const TABLE: [Option<u64>; 7] = crate::inverse_table!(7);
+        assert_eq!(TABLE, [None, Some(1), Some(4), Some(5), Some(2), Some(3), Some(6)]);
+        assert_eq!(crate::inverse_table!(1), [Some(0)]);
+
+        const INVERSE_OF_3: Option<u64> = const_inverse(3, 1_000_000_007);
+        assert_eq!(INVERSE_OF_3, Some(333_333_336));
+        for m in 0..40 {
+            for a in 0..45 {
+                assert_eq!(const_inverse(a, m), inverse(a, m));
+            }
+        }
+    }
+
+    #[test]
+    fn inverse_fixed_matches_inverse_under_the_built_in_modulus() {
+        for a in [0, 1, 2, 3, 12_345, MODULUS - 1, MODULUS, MODULUS + 2, u64::MAX] {
+            assert_eq!(inverse_fixed(a), crate::inverse(a, MODULUS), "a = {}", a);
+        }
+
+        //worked out while compiling
+        const TWO: Option<u64> = inverse_fixed(2);
+        assert_eq!(TWO, crate::inverse(2, MODULUS));
+    }
+
+    #[test]
+    fn parse_modulus_reads_decimal_numbers() {
+        assert_eq!(parse_modulus("998244353"), 998_244_353);
+        assert_eq!(parse_modulus("18446744073709551615"), u64::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "INVERSE_MODULUS must be a decimal number")]
+    fn parse_modulus_rejects_hex() {
+        parse_modulus("0x10");
+    }
+
+    #[test]
+    #[should_panic(expected = "INVERSE_MODULUS doesn't fit in a u64")]
+    fn parse_modulus_rejects_overflow() {
+        parse_modulus("18446744073709551616");
+    }
+
+    #[test]
+    fn multiplication_table_of_5() {
+        assert_eq!(multiplication_table(5), vec![
+            vec![0, 0, 0, 0, 0],
+            vec![0, 1, 2, 3, 4],
+            vec![0, 2, 4, 1, 3],
+            vec![0, 3, 1, 4, 2],
+            vec![0, 4, 3, 2, 1]
+        ]);
+        assert_eq!(inverse_positions(5), vec![(1, 1), (2, 3), (3, 2), (4, 4)]);
+        assert!(multiplication_table(0).is_empty());
+    }
+
+    #[test]
+    fn inverse_positions_match_inverse_pairs() {
+        for m in [1, 2, 5, 10, 12, 36] {
+            assert_eq!(inverse_positions(m), crate::inverse_pairs(m), "m = {}", m);
+        }
+    }
+
+    #[test]
+    fn sparse_list_matches_non_units_mod() {
+        assert_eq!(sparse_non_invertible(10), vec![0, 2, 4, 5, 6, 8]);
+        for m in [1, 2, 7, 12, 30, 97, 360, 1001] {
+            assert_eq!(sparse_non_invertible(m), crate::non_units_mod(m), "m = {}", m);
+        }
+    }
+
+    #[test]
+    fn sparse_lookup_agrees_with_the_gcd() {
+        for m in [10, 97, 360] {
+            let sparse = sparse_non_invertible(m);
+            for a in 0..m {
+                assert_eq!(is_invertible_via_sparse(a, &sparse), crate::inverse(a, m).is_some(), "a = {}, m = {}", a, m);
+            }
+        }
+    }
+}
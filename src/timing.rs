@@ -0,0 +1,84 @@
+use crate::is_relatively_prime;
+use std::time::{Duration, Instant};
+
+//dev:      How long each part of an inverse calculation took.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InverseTiming {
+    //the separate gcd calculation that checks a and m are relatively prime
+    pub coprimality_check: Duration,
+    //the Extended Euclidean loop itself
+    pub main_loop: Duration,
+    //moving a negative x into [0, m)
+    pub normalization: Duration,
+}
+
+impl InverseTiming {
+    pub fn total(&self) -> Duration {
+        self.coprimality_check + self.main_loop + self.normalization
+    }
+}
+
+//dev:      This function follows the same steps as modular_multiplicative_inverse and times each of them.
+//          The loop already ends with the gcd in A, so the up-front coprimality check repeats work; this shows what
+//          that repetition costs.
+//returns:  Returns the inverse of a under Mod m (None if it doesn't exist or m is 0) and the time spent on each step.
+#[allow(non_snake_case)]
+pub fn inverse_timed(a: u64, m: u64) -> (Option<u64>, InverseTiming) {
+    let mut timing = InverseTiming::default();
+
+    let start = Instant::now();
+    let coprime = m != 0 && is_relatively_prime(a, m);
+    timing.coprimality_check = start.elapsed();
+
+    if !coprime {
+        return (None, timing);
+    }
+
+    let start = Instant::now();
+    let mut x: i128 = 0;
+    let mut y: i128 = 1;
+    let mut A = m;
+    let mut B = a % m;
+
+    while B > 0 {
+        let q = A / B;
+        let r = A % B;
+        let t = x - y * q as i128;
+
+        A = B;
+        B = r;
+        x = y;
+        y = t;
+    }
+    timing.main_loop = start.elapsed();
+
+    let start = Instant::now();
+    if x < 0 {
+        x += m as i128;
+    }
+    let inverse = x as u64;
+    timing.normalization = start.elapsed();
+
+    (Some(inverse), timing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inverse_timed_reports_each_phase() {
+        let (inverse, timing) = inverse_timed(3, 7);
+        assert_eq!(inverse, Some(5));
+        assert!(timing.total() > Duration::ZERO);
+        assert_eq!(timing.total(), timing.coprimality_check + timing.main_loop + timing.normalization);
+
+        assert_eq!(inverse_timed(12200160415121876738, 7540113804746346429).0, crate::inverse(12200160415121876738, 7540113804746346429));
+
+        //without an inverse the loop never starts
+        let (inverse, timing) = inverse_timed(4, 10);
+        assert_eq!(inverse, None);
+        assert_eq!(timing.main_loop, Duration::ZERO);
+        assert_eq!(inverse_timed(3, 0).0, None);
+    }
+}
@@ -0,0 +1,76 @@
+use std::process::{Command, Output};
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_kvstore")).args(args).output().expect("failed to run the kvstore binary")
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).trim_end().to_string()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).trim_end().to_string()
+}
+
+#[test]
+fn prints_the_inverse() {
+    let output = run(&["3", "7"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "The modular multiplicative inverse of 3 Mod 7 is 5");
+}
+
+#[test]
+fn accepts_modulus_expressions() {
+    let output = run(&["2", "1e9+7"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "The modular multiplicative inverse of 2 Mod 1000000007 is 500000004");
+
+    //past i64::MAX the coefficients no longer fit in the old i64 loop
+    let output = run(&["3", "1e19"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "The modular multiplicative inverse of 3 Mod 10000000000000000000 is 6666666666666666667");
+}
+
+#[test]
+fn reports_a_missing_inverse_with_status_2() {
+    let output = run(&["4", "10"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(stderr(&output), "4 and 10 aren't relatively prime (gcd is 2)");
+    assert!(output.stdout.is_empty());
+
+    assert_eq!(run(&["4", "0"]).status.code(), Some(2));
+}
+
+#[test]
+fn rejects_malformed_numbers_with_status_2() {
+    let output = run(&["3", "1e9+"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).starts_with("invalid number '1e9+'"));
+
+    assert_eq!(run(&["x", "7"]).status.code(), Some(2));
+}
+
+#[test]
+fn reads_and_prints_hex_with_base_16() {
+    //0xff = 255 and 0x101 = 257, and 255 * 128 = 32640 = 127 * 257 + 1
+    let output = run(&["ff", "101", "--base", "16"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "The modular multiplicative inverse of ff Mod 101 is 80");
+
+    let output = run(&["--base", "16", "FF", "101", "--out-base", "10"]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "The modular multiplicative inverse of 255 Mod 257 is 128");
+
+    let output = run(&["3", "7", "--out-base", "2"]);
+    assert_eq!(stdout(&output), "The modular multiplicative inverse of 11 Mod 111 is 101");
+}
+
+#[test]
+fn rejects_bad_bases_with_status_2() {
+    let output = run(&["ff", "101", "--base", "37"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(stderr(&output), "the base must be a number from 2 to 36");
+
+    assert_eq!(run(&["ff", "101", "--base"]).status.code(), Some(2));
+    assert_eq!(run(&["fg", "101", "--base", "16"]).status.code(), Some(2));
+}